@@ -1,5 +1,7 @@
 use std::error::Error;
 use std::fmt;
+use std::io;
+use std::path::PathBuf;
 
 
 /// Error that occurs when a feature name is unsupported by this crate currently.
@@ -73,3 +75,40 @@ impl fmt::Display for VersionCheckError
         f.write_str(self.description())
     }
 }
+
+
+/// Error that occurs when a file of features names cannot be read.
+///
+/// (Actually private to the crate, not part of public API.  Is only `pub` for old Rust versions.)
+#[derive(Debug)]
+pub struct FeaturesFileError
+{
+    /// The file that was attempted.
+    pub path:  PathBuf,
+    /// Why it could not be read.
+    pub error: io::Error,
+}
+
+impl Error for FeaturesFileError
+{
+    fn description(&self) -> &str
+    {
+        "features file error"
+    }
+
+    fn cause(&self) -> Option<&Error>
+    {
+        Some(&self.error)
+    }
+}
+
+impl fmt::Display for FeaturesFileError
+{
+    fn fmt<'f>(
+        &self,
+        f: &mut fmt::Formatter<'f>,
+    ) -> fmt::Result
+    {
+        write!(f, "could not read features file {:?}: {}", self.path, self.error)
+    }
+}
@@ -1,3 +1,7 @@
+use std::env;
+use std::ffi::OsStr;
+
+
 /// Print to `stdout` a build-script instruction for Cargo.
 ///
 /// # Panics
@@ -20,6 +24,101 @@ pub fn emit_cargo_instruction(
     );
 }
 
+/// Whether the given `CARGO_CFG_*` environment variable, as set by Cargo for build scripts, has
+/// the given value among its comma-separated values, or, if no value is given, is non-empty.
+///
+/// (Actually private to the crate, not part of public API.  Is only `pub` for old Rust versions.)
+pub fn cargo_cfg_has(
+    var: &str,
+    value: Option<&str>,
+) -> bool
+{
+    match (env::var(var), value) {
+        (Ok(values), Some(value)) => values.split(',').any(|v| v == value),
+        (Ok(values), None) => !values.is_empty(),
+        (Err(_), _) => false,
+    }
+}
+
+/// The value of the given `CARGO_CFG_*` environment variable, sanitized for use as part of a
+/// `cfg` option name, or `None` if not set or empty.
+///
+/// (Actually private to the crate, not part of public API.  Is only `pub` for old Rust versions.)
+pub fn cargo_cfg_tag(var: &str) -> Option<String>
+{
+    match env::var(var) {
+        Ok(ref value) if !value.is_empty() => Some(
+            value
+                .chars()
+                .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Whether the build target differs from the host, as told by the `TARGET` and `HOST`
+/// environment variables that Cargo sets for build scripts.  When `TARGET` is set but `HOST` is
+/// not, this cannot be known and so is assumed.  When `TARGET` is not set, the target is the
+/// host.
+///
+/// (Actually private to the crate, not part of public API.  Is only `pub` for old Rust versions.)
+pub fn is_cross_compiling() -> bool
+{
+    match (env::var_os("TARGET"), env::var_os("HOST")) {
+        (Some(target), Some(host)) => target != host,
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
+/// Call the given function while the given environment variable is set to the given value, and
+/// then restore the variable to how it was before.
+///
+/// (Actually private to the crate, not part of public API.  Is only `pub` for old Rust versions.)
+pub fn with_env_var<T, F: FnOnce() -> T>(
+    var: &str,
+    value: &OsStr,
+    f: F,
+) -> T
+{
+    let previous = env::var_os(var);
+    env::set_var(var, value);
+    let result = f();
+    match previous {
+        Some(ref previous) => env::set_var(var, previous),
+        None => env::remove_var(var),
+    }
+    result
+}
+
+/// Split a list of features names, separated by commas and/or whitespace, into the names.  Each
+/// name is trimmed of surrounding whitespace, and empty names (e.g. from consecutive separators)
+/// are dropped.
+///
+/// (Actually private to the crate, not part of public API.  Is only `pub` for old Rust versions.)
+pub fn split_features_names(list: &str) -> Vec<String>
+{
+    list.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Split a list of features names, with one per line, into the names.  Anything after a `#` on a
+/// line is a comment.  Each name is trimmed of surrounding whitespace, and blank lines are
+/// dropped.
+///
+/// (Actually private to the crate, not part of public API.  Is only `pub` for old Rust versions.)
+pub fn lines_features_names(text: &str) -> Vec<String>
+{
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect()
+}
+
 /// Tell Cargo to display the given warning message after a build script has finished running.
 pub fn emit_warning(message: &str)
 {
@@ -194,13 +194,18 @@ mod helpers;
 mod recognized;
 
 use std::borrow::Borrow;
-use std::collections::{HashMap, HashSet};
+use std::collections::{hash_map, HashMap, HashSet};
+use std::env;
 use std::error::Error;
+use std::fmt;
+use std::fs::File;
 use std::hash::Hash;
+use std::io::Read;
 use std::iter::FromIterator;
+use std::path::{Path, PathBuf};
 
 pub use errors::UnsupportedFeatureTodoError;
-use errors::{unsupported_feature_todo_error, VersionCheckError};
+use errors::{unsupported_feature_todo_error, FeaturesFileError, VersionCheckError};
 pub use helpers::emit_warning;
 use recognized::Probe;
 
@@ -218,6 +223,42 @@ pub type FeatureEnabled = Option<FeatureCategories>;
 /// Indicates whether each from a set of features was found to be enabled and its categories.
 pub type EnabledFeatures<F> = HashMap<F, FeatureEnabled>;
 
+/// How it was determined whether a feature is enabled.
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
+pub enum ProbeMethod
+{
+    /// Compiled an expression with `rustc`.
+    Expr,
+    /// Compiled a type with `rustc`.
+    Type,
+    /// Compiled a path with `rustc`.
+    Path,
+    /// Compared the version of `rustc` with the version in which the feature was stabilized,
+    /// without compiling.
+    VersionTable,
+    /// Inspected a `CARGO_CFG_*` environment variable that describes the build target.
+    CargoCfg,
+    /// The feature is always enabled.
+    AlwaysEnabled,
+    /// Checked the release channel of `rustc`.
+    Channel,
+    /// Had no alternatives to try, and so is not enabled.  (When there are alternatives, the
+    /// method of the one that decided is given instead.)
+    Fallback,
+}
+
+/// Whether a feature is enabled, its categories, and how that was determined.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct ProbeOutcome
+{
+    /// Whether the feature was found to be enabled.
+    pub enabled:    bool,
+    /// How that was determined.
+    pub method:     ProbeMethod,
+    /// The categories of the feature, regardless of whether it is enabled.
+    pub categories: FeatureCategories,
+}
+
 /// Rust 1.0.0 does not support the `dyn` keyword.  This helps be clearer.
 pub type ResultDynErr<T> = Result<T, Box<Error>>;
 
@@ -229,6 +270,9 @@ pub type ResultDynErr<T> = Result<T, Box<Error>>;
 /// names.  Also calls [`emit_rerun_if_changed_file`] with the name of the file in which this
 /// macro was invoked.
 ///
+/// If `categories = [...]` is also given, calls [`CfgRustFeatures::emit_multiple_filtered`]
+/// instead, so that only the options of those categories are emitted.
+///
 /// # Examples
 /// A `build.rs` can be as simple as:
 /// ```no_run
@@ -236,12 +280,21 @@ pub type ResultDynErr<T> = Result<T, Box<Error>>;
 ///     cfg_rust_features::emit!(["iter_zip"]).unwrap();
 /// }
 /// ```
+///
+/// Or, to emit only the `rust_lib_feature` options:
+/// ```no_run
+/// cfg_rust_features::emit!(["iter_zip", "never_type"], categories = ["lib"]).unwrap();
+/// ```
 #[macro_export]
 macro_rules! emit {
     ($features_names:expr) => {{
         $crate::emit_rerun_if_changed_file(file!());
         $crate::CfgRustFeatures::emit($features_names).map(|_| ())
     }};
+    ($features_names:expr, categories = $categories:expr) => {{
+        $crate::emit_rerun_if_changed_file(file!());
+        $crate::CfgRustFeatures::emit_filtered($features_names, &$categories).map(|_| ())
+    }};
 }
 
 
@@ -265,16 +318,21 @@ pub fn emit_rerun_if_changed_file(filename: &str)
 #[derive(Debug)]
 pub struct CfgRustFeatures
 {
-    /// Result of a run of the [`autocfg`] crate's information gathering.
-    autocfg:       autocfg::AutoCfg,
-    /// Result of a run of the [`version_check`] crate's information gathering.
-    version_check: VersionCheck,
+    /// Result of a run of the [`autocfg`] crate's information gathering.  `None` when that
+    /// failed and [`OnProbeError::AssumeDisabled`] (or similar) was chosen.
+    autocfg:       Option<autocfg::AutoCfg>,
+    /// Result of a run of the [`version_check`] crate's information gathering.  `None` likewise.
+    version_check: Option<VersionCheck>,
+    /// See [`CfgRustFeaturesBuilder::prefer_version_table`].
+    prefer_version_table: bool,
+    /// Whether features known to be stable are decided without compiling probes.  Always `true`,
+    /// except for tests that must compile the probes.
+    skip_known_stable: bool,
 }
 
 #[derive(Debug)]
 struct VersionCheck
 {
-    #[allow(dead_code)]
     version: version_check::Version,
     channel: version_check::Channel,
     #[allow(dead_code)]
@@ -291,6 +349,15 @@ impl CfgRustFeatures
         Ok(try!(try!(CfgRustFeatures::new()).emit_multiple(features_names)))
     }
 
+    /// Convenience that calls [`Self::emit_multiple_filtered`] on a temporary instance.
+    pub fn emit_filtered<F: FeatureName, I: IntoIterator<Item = F>>(
+        features_names: I,
+        categories: &[&str],
+    ) -> ResultDynErr<EnabledFeatures<F>>
+    {
+        Ok(try!(try!(CfgRustFeatures::new()).emit_multiple_filtered(features_names, categories)))
+    }
+
     /// Gather the information about the current Rust compiler, and return a new instance that can
     /// perform the operations with it.
     ///
@@ -304,12 +371,24 @@ impl CfgRustFeatures
         Self::with_autocfg(try!(autocfg::AutoCfg::new()))
     }
 
+    /// Return a new builder, for creating an instance with non-default options.
+    pub fn builder() -> CfgRustFeaturesBuilder
+    {
+        CfgRustFeaturesBuilder::default()
+    }
+
     fn with_autocfg(autocfg: autocfg::AutoCfg) -> ResultDynErr<Self>
     {
         if let Some((version, channel, date)) = version_check::triple() {
             Ok(CfgRustFeatures {
-                autocfg:       autocfg,
-                version_check: VersionCheck { version: version, channel: channel, date: date },
+                autocfg:              Some(autocfg),
+                version_check:        Some(VersionCheck {
+                    version: version,
+                    channel: channel,
+                    date:    date,
+                }),
+                prefer_version_table: false,
+                skip_known_stable:    true,
             })
         }
         else {
@@ -317,6 +396,18 @@ impl CfgRustFeatures
         }
     }
 
+    /// An instance without any information about the Rust compiler, for when that could not be
+    /// gathered.  All features that would need `rustc` are considered not enabled.
+    fn without_rustc() -> Self
+    {
+        CfgRustFeatures {
+            autocfg:              None,
+            version_check:        None,
+            prefer_version_table: false,
+            skip_known_stable:    true,
+        }
+    }
+
     /// Write, to `stdout`, instructions for Cargo to set configuration options that indicate
     /// whether the currently-used version of Rust (compiler, language, and library) has enabled
     /// the given sequence of features.
@@ -332,6 +423,32 @@ impl CfgRustFeatures
     /// the feature according to whether it pertains to the compiler (`rust_comp_feature`), the
     /// language (`rust_lang_feature`), or the standard library (`rust_lib_feature`).
     ///
+    /// When a `stable` (or `beta`) `rustc` is comfortably past the version in which a feature was
+    /// stabilized, that feature is known to be enabled without compiling a probe for it, unless
+    /// the build target lacks `std` or is not the host.
+    ///
+    /// Some extra feature names, like `"target_has_atomic"` or `"wasm"`, are not probed with
+    /// `rustc` but are instead determined from the `CARGO_CFG_*` environment variables that Cargo
+    /// sets for build scripts.  These describe the build target, and they are never enabled when
+    /// those variables are not set (i.e. when not run as a build script by Cargo).  Some of
+    /// these, like `"target_abi"`, are also emitted with the value tagged onto the name, with
+    /// non-alphanumeric characters replaced by `_` (e.g. `rust_comp_feature =
+    /// "target_abi_eabihf"`).
+    ///
+    /// Probes are compiled for the build target, as given by the `TARGET` environment variable
+    /// that Cargo sets for build scripts, and so features that are only provided for some
+    /// platforms, like `"atomic_from_mut"`, `"io_safety"`, or `"windows_io_safety"`, are
+    /// correctly not enabled when cross-compiling for others.
+    ///
+    /// Probes are compiled as the default edition of Rust, except for features that depend on a
+    /// later edition.  E.g. `"closure_captures"` is probed as the 2021 edition, and so it tells
+    /// whether `rustc` provides that edition's disjoint closure captures, regardless of the
+    /// edition of the package that uses the option.
+    ///
+    /// Similarly, features that are about what `core` provides are probed as `#![no_std]`.  E.g.
+    /// `"core_net"` tells whether the IP address types are in `core`, and not only in `std`, for
+    /// `no_std` packages.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -347,17 +464,95 @@ impl CfgRustFeatures
     /// #     fn make_try_work() -> ResultDynErr<()> {
     /// let gathered_info_instance = try!(CfgRustFeatures::new());
     /// let enabled_features = try!(gathered_info_instance.emit_multiple(vec![
+    ///     "allocator_api",
+    ///     "anonymous_pipe",
     ///     "arbitrary_self_types",
+    ///     "atomic_from_mut",
+    ///     "available_parallelism",
+    ///     "backtrace",
+    ///     "binary_heap_drain_sorted",
+    ///     "binary_heap_into_iter_sorted",
+    ///     "black_box",
+    ///     "btree_cursors",
     ///     "cfg_version",
+    ///     "closure_captures",
+    ///     "cmp_minmax",
+    ///     "const_assert",
+    ///     "const_cstr_methods",
+    ///     "const_fn_float_arithmetic",
+    ///     "const_let_else",
+    ///     "const_panic",
+    ///     "const_slice_first",
+    ///     "core_net",
     ///     "destructuring_assignment",
+    ///     "dev_compiler",
+    ///     "diagnostic_namespace",
+    ///     "div_duration",
+    ///     "duration_millis_float",
+    ///     "entry_insert",
+    ///     "error_generic_member_access",
     ///     "error_in_core",
+    ///     "error_reporter",
+    ///     "exclusive_wrapper",
+    ///     "exit_status_error",
+    ///     "extract_if",
+    ///     "generic_nonzero",
+    ///     "hash_raw_entry",
+    ///     "hash_set_entry",
+    ///     "impl_trait_in_assoc_type",
+    ///     "inline_const_pat",
     ///     "inner_deref",
+    ///     "io_error_more",
+    ///     "io_error_other",
+    ///     "io_safety",
+    ///     "is_none_or",
+    ///     "iter_advance_by",
+    ///     "iter_array_chunks",
+    ///     "iter_collect_into",
+    ///     "iter_intersperse",
+    ///     "iter_map_windows",
+    ///     "iter_next_chunk",
+    ///     "iter_partition_in_place",
     ///     "iter_zip",
+    ///     "iterator_try_collect",
+    ///     "linked_list_cursors",
+    ///     "map_first_last",
+    ///     "maybe_uninit_slice",
+    ///     "maybe_uninit_uninit_array",
+    ///     "mpmc_channel",
+    ///     "mutex_unpoison",
     ///     "never_type",
+    ///     "new_uninit",
+    ///     "option_get_or_insert_default",
+    ///     "option_take_if",
+    ///     "panic_info_message",
+    ///     "panic_update_hook",
+    ///     "pattern",
+    ///     "portable_simd",
+    ///     "process_exitcode",
     ///     "question_mark",
+    ///     "raw_ref_macros",
+    ///     "read_buf",
+    ///     "result_flattening",
+    ///     "scoped_threads",
+    ///     "slice_as_chunks",
+    ///     "slice_fill",
+    ///     "slice_fill_with",
+    ///     "slice_first_last_chunk",
+    ///     "slice_take",
     ///     "step_trait",
+    ///     "sync_exclusive",
+    ///     "thread_sleep_until",
+    ///     "try_find",
+    ///     "try_reserve",
+    ///     "try_trait_v2",
     ///     "unwrap_infallible",
     ///     "unstable_features",
+    ///     "vec_into_raw_parts",
+    ///     "vec_push_within_capacity",
+    ///     "vec_split_at_spare",
+    ///     "windows_io_safety",
+    ///     "write_all_vectored",
     /// ]));
     /// #         Ok(())
     /// #     }
@@ -369,25 +564,49 @@ impl CfgRustFeatures
     ///
     /// or, with `rustc` version `1.56`, will write to `stdout`:
     /// ```text
+    /// cargo:rustc-cfg=rust_lang_feature="closure_captures"
     /// cargo:rustc-cfg=rust_lang_feature="question_mark"
+    /// cargo:rustc-cfg=rust_lib_feature="const_slice_first"
     /// cargo:rustc-cfg=rust_lib_feature="inner_deref"
+    /// cargo:rustc-cfg=rust_lib_feature="raw_ref_macros"
+    /// cargo:rustc-cfg=rust_lib_feature="slice_fill"
+    /// cargo:rustc-cfg=rust_lib_feature="slice_fill_with"
     /// ```
     ///
     /// or, with `rustc` version `1.59`, will write to `stdout`:
     /// ```text
+    /// cargo:rustc-cfg=rust_lang_feature="closure_captures"
+    /// cargo:rustc-cfg=rust_lang_feature="const_assert"
+    /// cargo:rustc-cfg=rust_lang_feature="const_panic"
     /// cargo:rustc-cfg=rust_lang_feature="destructuring_assignment"
     /// cargo:rustc-cfg=rust_lang_feature="question_mark"
-    /// cargo:rustc-cfg=rust_lib_feature="iter_zip"
+    /// cargo:rustc-cfg=rust_lib_feature="available_parallelism"
+    /// cargo:rustc-cfg=rust_lib_feature="const_slice_first"
     /// cargo:rustc-cfg=rust_lib_feature="inner_deref"
+    /// cargo:rustc-cfg=rust_lib_feature="iter_zip"
+    /// cargo:rustc-cfg=rust_lib_feature="raw_ref_macros"
+    /// cargo:rustc-cfg=rust_lib_feature="slice_fill"
+    /// cargo:rustc-cfg=rust_lib_feature="slice_fill_with"
+    /// cargo:rustc-cfg=rust_lib_feature="try_reserve"
     /// ```
     ///
     /// or, with `rustc` version `1.61.0-nightly`, will write to `stdout`:
     /// ```text
     /// cargo:rustc-cfg=rust_comp_feature="unstable_features"
+    /// cargo:rustc-cfg=rust_lang_feature="closure_captures"
+    /// cargo:rustc-cfg=rust_lang_feature="const_assert"
+    /// cargo:rustc-cfg=rust_lang_feature="const_panic"
     /// cargo:rustc-cfg=rust_lang_feature="destructuring_assignment"
     /// cargo:rustc-cfg=rust_lang_feature="question_mark"
+    /// cargo:rustc-cfg=rust_lib_feature="available_parallelism"
+    /// cargo:rustc-cfg=rust_lib_feature="const_slice_first"
     /// cargo:rustc-cfg=rust_lib_feature="inner_deref"
     /// cargo:rustc-cfg=rust_lib_feature="iter_zip"
+    /// cargo:rustc-cfg=rust_lib_feature="process_exitcode"
+    /// cargo:rustc-cfg=rust_lib_feature="raw_ref_macros"
+    /// cargo:rustc-cfg=rust_lib_feature="slice_fill"
+    /// cargo:rustc-cfg=rust_lib_feature="slice_fill_with"
+    /// cargo:rustc-cfg=rust_lib_feature="try_reserve"
     /// ```
     ///
     /// # Returns
@@ -418,6 +637,83 @@ impl CfgRustFeatures
         Ok(enabled_features)
     }
 
+    /// Like [`Self::emit_multiple`] but with the features names read from the given environment
+    /// variable (e.g. `CFG_RUST_FEATURES`).  This enables overriding which features are probed
+    /// without editing the build script.
+    ///
+    /// The variable's value is a list of names separated by commas and/or whitespace (e.g.
+    /// `"iter_zip, never_type"` or `"iter_zip never_type"`).  Surrounding whitespace is trimmed
+    /// and empty names are ignored.  If the variable is not set (or not valid Unicode), or is
+    /// empty, nothing is probed.
+    ///
+    /// Also tells Cargo to rerun the build script when the variable changes.
+    ///
+    /// # Returns
+    /// Same as [`Self::emit_multiple`], which is empty if nothing was probed.
+    ///
+    /// # Errors
+    /// Same as [`Self::emit_multiple`].
+    pub fn emit_from_env(
+        &self,
+        var: &str,
+    ) -> Result<EnabledFeatures<String>, UnsupportedFeatureTodoError>
+    {
+        helpers::emit_cargo_instruction("rerun-if-env-changed", Some(var));
+        let features_names = env::var(var).map(|list| helpers::split_features_names(&list));
+        self.emit_multiple(features_names.unwrap_or_else(|_| vec![]))
+    }
+
+    /// Like [`Self::emit_multiple`] but with the features names read from the given file (e.g. a
+    /// `features.txt` shared by the build scripts of several packages of a workspace).
+    ///
+    /// The file has one name per line.  Anything after a `#` on a line is a comment and is
+    /// ignored.  Surrounding whitespace is trimmed and blank lines are ignored.
+    ///
+    /// Also tells Cargo to rerun the build script when the file changes.
+    ///
+    /// # Returns
+    /// Same as [`Self::emit_multiple`].
+    ///
+    /// # Errors
+    /// If the file cannot be read (e.g. is missing), with a message that names the file.  Or,
+    /// same as [`Self::emit_multiple`].
+    pub fn emit_from_file(
+        &self,
+        path: &Path,
+    ) -> ResultDynErr<EnabledFeatures<String>>
+    {
+        helpers::emit_cargo_instruction("rerun-if-changed", Some(&path.to_string_lossy()));
+
+        let mut text = String::new();
+        let _ = try!(File::open(path)
+            .and_then(|mut file| file.read_to_string(&mut text))
+            .map_err(|error| FeaturesFileError { path: path.to_path_buf(), error: error }));
+        Ok(try!(self.emit_multiple(helpers::lines_features_names(&text))))
+    }
+
+    /// Like [`Self::emit_multiple`] but only writes the configuration options of the given
+    /// categories (e.g. `&["lib"]`).  All the given features are still probed, and the returned
+    /// value still indicates all of their categories.
+    ///
+    /// # Returns
+    /// Same as [`Self::emit_multiple`].
+    ///
+    /// # Errors
+    /// Same as [`Self::emit_multiple`].
+    pub fn emit_multiple_filtered<F: FeatureName, I: IntoIterator<Item = F>>(
+        &self,
+        features_names: I,
+        categories: &[&str],
+    ) -> Result<EnabledFeatures<F>, UnsupportedFeatureTodoError>
+    {
+        let enabled_features = try!(self.probe_multiple(features_names));
+
+        for (name, enabled) in &enabled_features {
+            self.emit_single(name.borrow(), &filter_categories(enabled, categories));
+        }
+        Ok(enabled_features)
+    }
+
     /// Like [`Self::emit_multiple`] but does not write anything.  Use when only the return value
     /// is of interest.
     ///
@@ -440,6 +736,73 @@ impl CfgRustFeatures
         Ok(enabled_features)
     }
 
+    /// Like [`Self::probe_multiple`] but also indicates how it was determined whether each
+    /// feature is enabled.  Intended for diagnosing surprising results.
+    ///
+    /// # Returns
+    /// A [`HashMap`] that gives the [`ProbeOutcome`] of each of the given features.
+    ///
+    /// # Errors
+    /// Same as [`Self::emit_multiple`].
+    pub fn probe_multiple_detailed<F: FeatureName, I: IntoIterator<Item = F>>(
+        &self,
+        features_names: I,
+    ) -> Result<HashMap<F, ProbeOutcome>, UnsupportedFeatureTodoError>
+    {
+        let mut outcomes = HashMap::new();
+
+        for name in features_names {
+            let outcome = try!(self.probe_single_detailed(name.borrow()));
+            let _ = outcomes.insert(name, outcome);
+        }
+        Ok(outcomes)
+    }
+
+    /// Like [`Self::emit_multiple`] but for only a single feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate cfg_rust_features;
+    /// # extern crate create_temp_subdir;
+    /// # use cfg_rust_features::{CfgRustFeatures, ResultDynErr};
+    /// # use create_temp_subdir::TempSubDir;
+    /// #
+    /// # fn main() {
+    /// #     let dir = TempSubDir::new("doctest-emit_single_feature").unwrap();
+    /// #     std::env::set_var("OUT_DIR", &dir);
+    /// #
+    /// #     fn make_try_work() -> ResultDynErr<()> {
+    /// let gathered_info_instance = try!(CfgRustFeatures::new());
+    /// let enabled_feature = try!(gathered_info_instance.emit_single_feature("iter_zip"));
+    /// #         Ok(())
+    /// #     }
+    /// #     make_try_work().unwrap();
+    /// # }
+    /// ```
+    ///
+    /// with `rustc` version `1.56`, will write nothing to `stdout`.
+    ///
+    /// or, with `rustc` version `1.59`, will write to `stdout`:
+    /// ```text
+    /// cargo:rustc-cfg=rust_lib_feature="iter_zip"
+    /// ```
+    ///
+    /// # Returns
+    /// Same as [`Self::probe_single`].
+    ///
+    /// # Errors
+    /// Same as [`Self::probe_single`].
+    pub fn emit_single_feature(
+        &self,
+        feature_name: &str,
+    ) -> Result<FeatureEnabled, UnsupportedFeatureTodoError>
+    {
+        let enabled = try!(self.probe_single(feature_name));
+        self.emit_single(feature_name, &enabled);
+        Ok(enabled)
+    }
+
     fn emit_single(
         &self,
         feature_name: &str,
@@ -447,43 +810,468 @@ impl CfgRustFeatures
     )
     {
         if let &Some(ref categories) = enabled {
+            let names = Self::emitted_names(feature_name);
             for category in categories {
-                helpers::emit_rust_feature(category, feature_name);
+                for name in &names {
+                    helpers::emit_rust_feature(category, name);
+                }
             }
         }
     }
 
+    /// The names to emit for the given enabled feature.  Usually only its own name, but some
+    /// features are also emitted with a name tagged with a value that describes the build target.
+    fn emitted_names(feature_name: &str) -> Vec<String>
+    {
+        let mut names = vec![String::from(feature_name)];
+        if let Some(&recognized::Feature { probe: Probe::CargoCfgTagged(var), .. }) =
+            recognized::get(feature_name)
+        {
+            if let Some(tag) = helpers::cargo_cfg_tag(var) {
+                names.push(format!("{}_{}", feature_name, tag));
+            }
+        }
+        names
+    }
+
     /// Tests whether the current `rustc` provides the given compiler/language/library feature as
-    /// stable (i.e. without needing the `#![feature(...)]` of nightly).
+    /// stable (i.e. without needing the `#![feature(...)]` of nightly).  Does not write anything.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # extern crate cfg_rust_features;
+    /// # extern crate create_temp_subdir;
+    /// # use cfg_rust_features::{CfgRustFeatures, ResultDynErr};
+    /// # use create_temp_subdir::TempSubDir;
+    /// #
+    /// # fn main() {
+    /// #     let dir = TempSubDir::new("doctest-probe_single").unwrap();
+    /// #     std::env::set_var("OUT_DIR", &dir);
+    /// #
+    /// #     fn make_try_work() -> ResultDynErr<()> {
+    /// let gathered_info_instance = try!(CfgRustFeatures::new());
+    /// if try!(gathered_info_instance.probe_single("iter_zip")).is_some() {
+    ///     /* ... */
+    /// }
+    /// #         Ok(())
+    /// #     }
+    /// #     make_try_work().unwrap();
+    /// # }
+    /// ```
     ///
     /// # Returns
     /// The categories of the feature if the feature is enabled, or else `None`.
     ///
     /// # Errors
     /// If the feature name is unsupported by this crate currently.
-    fn probe_single(
+    pub fn probe_single(
         &self,
         feature_name: &str,
     ) -> Result<FeatureEnabled, UnsupportedFeatureTodoError>
+    {
+        let outcome = try!(self.probe_single_detailed(feature_name));
+        Ok(if outcome.enabled { Some(outcome.categories) } else { None })
+    }
+
+    fn probe_single_detailed(
+        &self,
+        feature_name: &str,
+    ) -> Result<ProbeOutcome, UnsupportedFeatureTodoError>
     {
         let feature = try!(
             recognized::get(feature_name)
                 .ok_or_else(|| unsupported_feature_todo_error(feature_name))
         );
-        let enabled = match feature.probe {
-            Probe::Expr(e) => self.autocfg.probe_expression(e),
-            Probe::Type(t) => self.autocfg.probe_type(t),
-            Probe::Path(p) => self.autocfg.probe_path(p),
-            Probe::AlwaysEnabled => true,
-            Probe::UnstableFeatures => self.version_check.channel.supports_features(),
+        let decided = match self.version_check {
+            Some(ref version_check) if self.version_table_applies() =>
+                if self.skip_known_stable && known_stable(feature.stabilized, version_check) {
+                    Some(true)
+                }
+                else if self.prefer_version_table {
+                    by_version_table(feature.stabilized, version_check)
+                }
+                else {
+                    None
+                },
+            _ => None,
         };
-        Ok(if enabled {
-            Some(HashSet::from_iter(feature.categories.iter().map(|&x| x)))
-        }
-        else {
-            None
+        let (enabled, method) = match decided {
+            Some(enabled) => (enabled, ProbeMethod::VersionTable),
+            None => self.probe(&feature.probe),
+        };
+        Ok(ProbeOutcome {
+            enabled:    enabled,
+            method:     method,
+            categories: HashSet::from_iter(feature.categories.iter().map(|&x| x)),
         })
     }
+
+    /// Whether features may be decided by the versions in which they were stabilized, without
+    /// compiling probes.  Not when the build target lacks `std` or is not the host, because a
+    /// version only tells that a feature became stable, not that it is provided for the target
+    /// (e.g. the many features of `std`).
+    fn version_table_applies(&self) -> bool
+    {
+        self.autocfg.as_ref().map_or(false, |a| !a.no_std()) && !helpers::is_cross_compiling()
+    }
+
+    /// Whether the current `rustc` is a `dev` (i.e. locally-built) compiler, as opposed to a
+    /// `nightly`, `beta`, or `stable` release.
+    ///
+    /// The `"dev_compiler"` feature name can also be given to [`Self::emit_multiple`] to emit
+    /// this as the `rust_comp_feature = "dev_compiler"` configuration option.
+    pub fn is_dev_compiler(&self) -> bool
+    {
+        self.version_check.as_ref().map_or(false, |v| v.channel.is_dev())
+    }
+
+    /// Tests whether the current `rustc` passes the given probe, and how that was determined.
+    fn probe(
+        &self,
+        probe: &Probe,
+    ) -> (bool, ProbeMethod)
+    {
+        self.probe_with(self.autocfg.as_ref(), probe)
+    }
+
+    /// Like [`Self::probe`] but compiles with the given `autocfg` instance, which might be
+    /// configured differently than `self.autocfg` (e.g. for another edition).  Without one,
+    /// nothing can be compiled and so such probes are not enabled.
+    fn probe_with(
+        &self,
+        autocfg: Option<&autocfg::AutoCfg>,
+        probe: &Probe,
+    ) -> (bool, ProbeMethod)
+    {
+        match *probe {
+            Probe::Expr(e) =>
+                (autocfg.map_or(false, |a| a.probe_expression(e)), ProbeMethod::Expr),
+            Probe::Type(t) => (autocfg.map_or(false, |a| a.probe_type(t)), ProbeMethod::Type),
+            Probe::Path(p) => (autocfg.map_or(false, |a| a.probe_path(p)), ProbeMethod::Path),
+            Probe::Fallback(probes) => {
+                let mut result = (false, ProbeMethod::Fallback);
+                for p in probes {
+                    result = self.probe_with(autocfg, p);
+                    if result.0 {
+                        break;
+                    }
+                }
+                result
+            },
+            Probe::CargoCfg(var, value) =>
+                (helpers::cargo_cfg_has(var, value), ProbeMethod::CargoCfg),
+            Probe::CargoCfgTagged(var) =>
+                (helpers::cargo_cfg_has(var, None), ProbeMethod::CargoCfg),
+            Probe::Edition(edition, probe) => match autocfg {
+                Some(autocfg) => {
+                    let mut autocfg = autocfg.clone();
+                    autocfg.set_edition(Some(String::from(edition)));
+                    self.probe_with(Some(&autocfg), probe)
+                },
+                None => self.probe_with(None, probe),
+            },
+            Probe::NoStd(probe) => match autocfg {
+                Some(autocfg) => {
+                    let mut autocfg = autocfg.clone();
+                    autocfg.set_no_std(true);
+                    self.probe_with(Some(&autocfg), probe)
+                },
+                None => self.probe_with(None, probe),
+            },
+            Probe::AlwaysEnabled => (true, ProbeMethod::AlwaysEnabled),
+            Probe::UnstableFeatures => (
+                self.version_check.as_ref().map_or(false, |v| v.channel.supports_features()),
+                ProbeMethod::Channel,
+            ),
+            Probe::DevCompiler => (self.is_dev_compiler(), ProbeMethod::Channel),
+        }
+    }
+}
+
+/// Keep only the given categories of an enabled feature.  Is `None` if none remain.
+fn filter_categories(
+    enabled: &FeatureEnabled,
+    categories: &[&str],
+) -> FeatureEnabled
+{
+    enabled.as_ref().and_then(|enabled| {
+        let kept: FeatureCategories =
+            enabled.iter().filter(|c| categories.contains(c)).cloned().collect();
+        if kept.is_empty() { None } else { Some(kept) }
+    })
+}
+
+/// Whether a feature is certainly enabled, so that compiling a probe can be skipped, because a
+/// `stable` (or `beta`) `rustc` is used whose version is at least one minor version past the
+/// version in which the feature was stabilized.  That margin guards against a recorded version
+/// being slightly off.  Returns `false` whenever there is any doubt, in which case a probe must
+/// still be compiled.
+fn known_stable(
+    stabilized: Option<&str>,
+    version_check: &VersionCheck,
+) -> bool
+{
+    match stabilized.and_then(version_check::Version::parse) {
+        Some(stabilized) if !version_check.channel.supports_features() => {
+            let (major, minor, _) = stabilized.to_mmp();
+            version_check.version >= version_check::Version::from_mmp(major, minor + 1, 0)
+        },
+        _ => false,
+    }
+}
+
+/// Decides whether a feature is enabled by comparing the version of `rustc` with the version in
+/// which the feature was stabilized, without compiling a probe.  Returns `None` when this cannot
+/// be decided, i.e. when the stabilization version is unknown or when a `nightly` (or `dev`)
+/// compiler is used (which might provide a feature before, or differently than, when it became
+/// stable).
+fn by_version_table(
+    stabilized: Option<&str>,
+    version_check: &VersionCheck,
+) -> Option<bool>
+{
+    match stabilized {
+        Some(stabilized) if !version_check.channel.supports_features() =>
+            Some(version_check.version.at_least(stabilized)),
+        _ => None,
+    }
+}
+
+
+/// Builder of [`CfgRustFeatures`] instances with non-default options.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use cfg_rust_features::CfgRustFeatures;
+/// let gathered_info_instance = CfgRustFeatures::builder().prefer_version_table(true).build();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CfgRustFeaturesBuilder
+{
+    prefer_version_table: bool,
+    on_probe_error:       OnProbeError,
+    rustc:                Option<PathBuf>,
+}
+
+/// What to do when the information about the Rust compiler cannot be gathered, e.g. because
+/// `rustc` cannot be run in a locked-down build environment.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum OnProbeError
+{
+    /// Return the error.  This is the default.
+    Strict,
+    /// Continue as if all features that would need `rustc` are not enabled, and emit a
+    /// `cargo:warning` that tells why.
+    AssumeDisabled,
+    /// Like `AssumeDisabled` but without the warning.
+    AssumeDisabledQuietly,
+}
+
+impl Default for OnProbeError
+{
+    fn default() -> Self
+    {
+        OnProbeError::Strict
+    }
+}
+
+impl CfgRustFeaturesBuilder
+{
+    /// Whether to decide if a feature is enabled by comparing the version of `rustc` with the
+    /// version in which the feature was stabilized, when that is known, instead of compiling a
+    /// probe.  This is much faster when there are many features.
+    ///
+    /// Probes are still compiled for features whose stabilization version is not known, and for
+    /// all features when a `nightly` (or `dev`) compiler is used, since such might provide a
+    /// feature before, or differently than, the version in which it became stable.  They are also
+    /// still compiled for all features when the build target lacks `std` or is not the host,
+    /// since a feature might not be provided for such a target regardless of the version.
+    ///
+    /// The default is `false`.
+    pub fn prefer_version_table(
+        mut self,
+        prefer: bool,
+    ) -> Self
+    {
+        self.prefer_version_table = prefer;
+        self
+    }
+
+    /// What to do if the information gathering fails.  With [`OnProbeError::AssumeDisabled`],
+    /// a build script can degrade gracefully where `rustc` cannot be run: the features that are
+    /// decided without `rustc` (e.g. `"rust1"`, or those from `CARGO_CFG_*` variables) are still
+    /// emitted, but all others are considered not enabled.
+    ///
+    /// Only a failure of the information gathering done by [`Self::build`] is handled by this.
+    /// (Failures of individual probes, after the information gathering succeeded, are always
+    /// considered to mean that the feature is not enabled, as `autocfg` does.)
+    ///
+    /// The default is [`OnProbeError::Strict`].
+    pub fn on_probe_error(
+        mut self,
+        on_probe_error: OnProbeError,
+    ) -> Self
+    {
+        self.on_probe_error = on_probe_error;
+        self
+    }
+
+    /// Probe the given Rust compiler, instead of the one given by the `RUSTC` environment
+    /// variable (or else the `rustc` in the `PATH`).  Useful for tooling that probes multiple
+    /// toolchains.
+    ///
+    /// This works by setting `RUSTC` to the given path while the information is gathered by
+    /// [`Self::build`], and restoring it afterward.  The `RUSTC_WRAPPER` (and
+    /// `RUSTC_WORKSPACE_WRAPPER`) environment variables are still respected, as usual, and so a
+    /// wrapper, if any, will be given this path as the compiler to run.
+    pub fn rustc(
+        mut self,
+        rustc: PathBuf,
+    ) -> Self
+    {
+        self.rustc = Some(rustc);
+        self
+    }
+
+    /// Gather the information about the current Rust compiler, and return a new instance with the
+    /// options of this builder.
+    ///
+    /// # Errors
+    /// Same as [`CfgRustFeatures::new`], unless [`Self::on_probe_error`] says otherwise.
+    pub fn build(self) -> ResultDynErr<CfgRustFeatures>
+    {
+        let gathered = match self.rustc {
+            Some(ref rustc) =>
+                helpers::with_env_var("RUSTC", rustc.as_os_str(), CfgRustFeatures::new),
+            None => CfgRustFeatures::new(),
+        };
+        self.finish(gathered)
+    }
+
+    fn finish(
+        self,
+        gathered: ResultDynErr<CfgRustFeatures>,
+    ) -> ResultDynErr<CfgRustFeatures>
+    {
+        let mut cfg_rust_features = match (gathered, self.on_probe_error) {
+            (Ok(cfg_rust_features), _) => cfg_rust_features,
+            (Err(error), OnProbeError::Strict) => return Err(error),
+            (Err(error), on_probe_error) => {
+                if on_probe_error == OnProbeError::AssumeDisabled {
+                    emit_warning(&format!(
+                        "Assuming that Rust features are not enabled, because probing failed: {}",
+                        error
+                    ));
+                }
+                CfgRustFeatures::without_rustc()
+            },
+        };
+        cfg_rust_features.prefer_version_table = self.prefer_version_table;
+        Ok(cfg_rust_features)
+    }
+}
+
+
+/// Iterate only the enabled features of an [`EnabledFeatures`], paired with their categories.
+///
+/// # Examples
+/// ```
+/// # use cfg_rust_features::{enabled_only, EnabledFeatures};
+/// let mut enabled_features = EnabledFeatures::new();
+/// enabled_features.insert("rust1", Some(vec!["comp", "lang", "lib"].into_iter().collect()));
+/// enabled_features.insert("never_type", None);
+///
+/// let enabled: Vec<_> = enabled_only(&enabled_features).map(|(&name, _)| name).collect();
+/// assert_eq!(enabled, ["rust1"]);
+/// ```
+pub fn enabled_only<'a, F: FeatureName>(
+    enabled_features: &'a EnabledFeatures<F>
+) -> EnabledOnly<'a, F>
+{
+    EnabledOnly(enabled_features.iter())
+}
+
+/// Iterator returned by [`enabled_only`].
+#[derive(Clone)]
+pub struct EnabledOnly<'a, F: 'a>(hash_map::Iter<'a, F, FeatureEnabled>);
+
+// (Old Rust versions do not `impl Debug` for `hash_map::Iter`, so it cannot be derived.)
+impl<'a, F> fmt::Debug for EnabledOnly<'a, F>
+{
+    fn fmt<'f>(
+        &self,
+        f: &mut fmt::Formatter<'f>,
+    ) -> fmt::Result
+    {
+        f.write_str("EnabledOnly { .. }")
+    }
+}
+
+impl<'a, F> Iterator for EnabledOnly<'a, F>
+{
+    type Item = (&'a F, &'a FeatureCategories);
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        for (name, enabled) in self.0.by_ref() {
+            if let Some(ref categories) = *enabled {
+                return Some((name, categories));
+            }
+        }
+        None
+    }
+}
+
+
+/// Human-readable summary of an [`EnabledFeatures`], e.g. for logging from a build script.
+///
+/// Its `Display` gives a line for each feature, sorted by name, that tells whether it is enabled
+/// and its categories (also sorted) if so.
+///
+/// # Examples
+/// ```
+/// # use cfg_rust_features::{EnabledFeatures, EnabledFeaturesSummary};
+/// let mut enabled_features = EnabledFeatures::new();
+/// enabled_features.insert("rust1", Some(vec!["lib", "lang"].into_iter().collect()));
+/// enabled_features.insert("never_type", None);
+/// assert_eq!(EnabledFeaturesSummary(&enabled_features).to_string(),
+///            "never_type: not enabled\nrust1: enabled (lang, lib)");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct EnabledFeaturesSummary<'a, F: 'a + FeatureName>(pub &'a EnabledFeatures<F>);
+
+impl<'a, F: FeatureName> fmt::Display for EnabledFeaturesSummary<'a, F>
+{
+    fn fmt<'f>(
+        &self,
+        f: &mut fmt::Formatter<'f>,
+    ) -> fmt::Result
+    {
+        let mut features: Vec<(&str, &FeatureEnabled)> =
+            self.0.iter().map(|(name, enabled)| (name.borrow(), enabled)).collect();
+        features.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (i, &(name, enabled)) in features.iter().enumerate() {
+            if i > 0 {
+                try!(f.write_str("\n"));
+            }
+            match *enabled {
+                Some(ref categories) => {
+                    let mut categories: Vec<&str> = categories.iter().cloned().collect();
+                    categories.sort();
+                    try!(write!(f, "{}: enabled (", name));
+                    for (j, category) in categories.iter().enumerate() {
+                        try!(write!(f, "{}{}", if j > 0 { ", " } else { "" }, category));
+                    }
+                    try!(f.write_str(")"));
+                },
+                None => try!(write!(f, "{}: not enabled", name)),
+            }
+        }
+        Ok(())
+    }
 }
 
 
@@ -491,7 +1279,12 @@ impl CfgRustFeatures
 mod tests
 {
     extern crate create_temp_subdir;
-    use super::{autocfg, CfgRustFeatures, ResultDynErr};
+    use self::create_temp_subdir::TempSubDir;
+    use std::path::Path;
+    use super::{autocfg, by_version_table, filter_categories, known_stable, CfgRustFeatures,
+                EnabledFeatures, EnabledFeaturesSummary, ProbeMethod, ResultDynErr,
+                VersionCheck};
+    use recognized::Probe;
 
     impl CfgRustFeatures
     {
@@ -501,6 +1294,15 @@ mod tests
             let ac = try!(autocfg::AutoCfg::with_dir(&out_dir));
             Self::with_autocfg(ac)
         }
+
+        /// Like `for_test`, but also returns the directory, which must be kept alive for as long
+        /// as the instance is used to probe, because `autocfg` writes into it.
+        fn for_test_with_dir(name: &str) -> ResultDynErr<(Self, TempSubDir)>
+        {
+            let out_dir = TempSubDir::new(name).unwrap();
+            let ac = try!(autocfg::AutoCfg::with_dir(&out_dir));
+            Self::with_autocfg(ac).map(|cfg_rust_features| (cfg_rust_features, out_dir))
+        }
     }
 
     #[test]
@@ -524,6 +1326,491 @@ mod tests
                     https://github.com/DerickEddington/cfg_rust_features");
     }
 
+    #[test]
+    fn fallback()
+    {
+        const LATER: Probe = Probe::Fallback(&[Probe::Type("Bogusness"), Probe::Type("u8")]);
+        const NONE: Probe =
+            Probe::Fallback(&[Probe::Type("Bogusness"), Probe::Path("bogus::ness")]);
+        const EMPTY: Probe = Probe::Fallback(&[]);
+
+        let (cfg_rust_features, _out_dir) =
+            CfgRustFeatures::for_test_with_dir("unittest-lib-fallback").unwrap();
+        assert_eq!(cfg_rust_features.probe(&LATER), (true, ProbeMethod::Type));
+        assert_eq!(cfg_rust_features.probe(&NONE), (false, ProbeMethod::Path));
+        assert_eq!(cfg_rust_features.probe(&EMPTY), (false, ProbeMethod::Fallback));
+    }
+
+    fn versioned(version: &str) -> VersionCheck
+    {
+        use version_check::{Channel, Date, Version};
+
+        VersionCheck {
+            version: Version::parse(version).unwrap(),
+            channel: Channel::parse(version).unwrap(),
+            date:    Date::parse("2022-03-01").unwrap(),
+        }
+    }
+
+    #[test]
+    fn edition()
+    {
+        use version_check::Version;
+
+        const DISJOINT: Probe = Probe::Expr(
+            "{ struct S { a: i32, b: i32 }
+               let mut s = S { a: 0, b: 0 };
+               let mut c = || s.a += 1;
+               let _ = &mut s.b;
+               c(); }",
+        );
+        const DISJOINT_2021: Probe = Probe::Edition("2021", &DISJOINT);
+        const BOGUS_EDITION: Probe = Probe::Edition("1999", &Probe::Type("u8"));
+
+        let (cfg_rust_features, _out_dir) =
+            CfgRustFeatures::for_test_with_dir("unittest-lib-edition").unwrap();
+        let version = &cfg_rust_features.version_check.as_ref().unwrap().version;
+        let has_2021 = *version >= Version::parse("1.56.0").unwrap();
+        assert_eq!(cfg_rust_features.probe(&DISJOINT), (false, ProbeMethod::Expr));
+        assert_eq!(cfg_rust_features.probe(&DISJOINT_2021), (has_2021, ProbeMethod::Expr));
+        assert_eq!(cfg_rust_features.probe(&BOGUS_EDITION), (false, ProbeMethod::Type));
+        // Only the edition of the given probe is affected.
+        assert_eq!(cfg_rust_features.probe(&DISJOINT), (false, ProbeMethod::Expr));
+    }
+
+    #[test]
+    fn no_std()
+    {
+        const STD_VEC: Probe = Probe::Path("std::vec::Vec");
+        const NO_STD_VEC: Probe = Probe::NoStd(&STD_VEC);
+        const NO_STD_CELL: Probe = Probe::NoStd(&Probe::Path("core::cell::Cell"));
+
+        let (cfg_rust_features, _out_dir) =
+            CfgRustFeatures::for_test_with_dir("unittest-lib-no_std").unwrap();
+        assert_eq!(cfg_rust_features.probe(&NO_STD_VEC), (false, ProbeMethod::Path));
+        assert_eq!(cfg_rust_features.probe(&NO_STD_CELL), (true, ProbeMethod::Path));
+        // Only the given probe is affected.
+        assert_eq!(cfg_rust_features.probe(&STD_VEC), (true, ProbeMethod::Path));
+    }
+
+    #[test]
+    fn version_table_not_used_for_no_std()
+    {
+        // Features that are only in `std`, and that are stable since versions old enough to be
+        // decided by the version table.
+        let features_names = vec!["available_parallelism", "process_exitcode"];
+
+        for &prefer in &[false, true] {
+            let (mut cfg_rust_features, _out_dir) =
+                CfgRustFeatures::for_test_with_dir("unittest-lib-no_std-version_table").unwrap();
+            // As `autocfg` sets when the build target, here the host, lacks `std`.
+            cfg_rust_features.autocfg.as_mut().unwrap().set_no_std(true);
+            cfg_rust_features.prefer_version_table = prefer;
+
+            let outcomes = cfg_rust_features.probe_multiple_detailed(features_names.clone());
+            for (name, outcome) in &outcomes.unwrap() {
+                assert!(outcome.method != ProbeMethod::VersionTable, "{}", name);
+                assert!(!outcome.enabled, "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn known_stable_boundaries()
+    {
+        assert!(!known_stable(Some("1.59.0"), &versioned("1.58.0")));
+        assert!(!known_stable(Some("1.59.0"), &versioned("1.59.0")));
+        assert!(!known_stable(Some("1.59.0"), &versioned("1.59.9")));
+        assert!(known_stable(Some("1.59.0"), &versioned("1.60.0")));
+        assert!(known_stable(Some("1.59.0"), &versioned("1.85.0")));
+        assert!(!known_stable(Some("1.59.0"), &versioned("1.59.0-beta")));
+        assert!(known_stable(Some("1.59.0"), &versioned("1.60.0-beta")));
+        assert!(!known_stable(Some("1.59.0"), &versioned("1.85.0-nightly")));
+        assert!(!known_stable(Some("1.59.0"), &versioned("1.85.0-dev")));
+        assert!(!known_stable(None, &versioned("1.85.0")));
+        assert!(!known_stable(Some("bogus"), &versioned("1.85.0")));
+    }
+
+    #[test]
+    fn on_probe_error()
+    {
+        use super::OnProbeError;
+
+        // Simulate failing to gather the information, via an invalid directory for probes.
+        fn failed() -> ResultDynErr<CfgRustFeatures>
+        {
+            let ac = try!(autocfg::AutoCfg::with_dir("/nonexistent/cfg_rust_features-unittest"));
+            CfgRustFeatures::with_autocfg(ac)
+        }
+
+        assert!(CfgRustFeatures::builder().finish(failed()).is_err());
+        assert!(CfgRustFeatures::builder()
+            .on_probe_error(OnProbeError::Strict)
+            .finish(failed())
+            .is_err());
+
+        for &on_probe_error in
+            &[OnProbeError::AssumeDisabled, OnProbeError::AssumeDisabledQuietly]
+        {
+            let builder = CfgRustFeatures::builder().on_probe_error(on_probe_error);
+            let cfg_rust_features = builder.finish(failed()).unwrap();
+            let enabled_features = cfg_rust_features
+                .probe_multiple(vec![
+                    "rust1",
+                    "closure_captures",
+                    "iter_zip",
+                    "never_type",
+                    "unstable_features",
+                ])
+                .unwrap();
+            assert!(enabled_features["rust1"].is_some());
+            assert!(enabled_features["closure_captures"].is_none());
+            assert!(enabled_features["iter_zip"].is_none());
+            assert!(enabled_features["never_type"].is_none());
+            assert!(enabled_features["unstable_features"].is_none());
+            assert!(!cfg_rust_features.is_dev_compiler());
+        }
+    }
+
+    #[test]
+    fn rustc()
+    {
+        use std::env;
+        use std::path::PathBuf;
+
+        let out_dir = TempSubDir::new("unittest-lib-rustc").unwrap();
+        let before = env::var_os("RUSTC");
+        let current = PathBuf::from(before.clone().unwrap_or_else(|| "rustc".into()));
+
+        // (`OUT_DIR` is restored afterward, so as to not affect the other tests.)
+        let cfg_rust_features = super::helpers::with_env_var("OUT_DIR", out_dir.as_ref(), || {
+            CfgRustFeatures::builder().rustc(current).build()
+        })
+        .unwrap();
+        assert!(cfg_rust_features.probe_single("rust1").unwrap().is_some());
+        assert!(cfg_rust_features.probe_single("question_mark").unwrap().is_some());
+        assert_eq!(env::var_os("RUSTC"), before);
+        // (A bogus path is not tested, because changing `RUSTC` to that would interfere with the
+        // other tests that run concurrently.)
+    }
+
+    #[test]
+    fn dev_compiler()
+    {
+        let (mut cfg_rust_features, _out_dir) =
+            CfgRustFeatures::for_test_with_dir("unittest-lib-dev_compiler").unwrap();
+        let enabled = |c: &CfgRustFeatures| {
+            let enabled_features = c.probe_multiple(vec!["dev_compiler", "unstable_features"]);
+            let enabled_features = enabled_features.unwrap();
+            (enabled_features["dev_compiler"].is_some(),
+             enabled_features["unstable_features"].is_some())
+        };
+
+        cfg_rust_features.version_check = Some(versioned("1.61.0-dev"));
+        assert!(cfg_rust_features.is_dev_compiler());
+        assert_eq!(enabled(&cfg_rust_features), (true, true));
+
+        cfg_rust_features.version_check = Some(versioned("1.61.0-nightly"));
+        assert!(!cfg_rust_features.is_dev_compiler());
+        assert_eq!(enabled(&cfg_rust_features), (false, true));
+
+        cfg_rust_features.version_check = Some(versioned("1.61.0"));
+        assert!(!cfg_rust_features.is_dev_compiler());
+        assert_eq!(enabled(&cfg_rust_features), (false, false));
+    }
+
+    #[test]
+    fn version_table()
+    {
+        assert_eq!(by_version_table(Some("1.59.0"), &versioned("1.60.0")), Some(true));
+        assert_eq!(by_version_table(Some("1.59.0"), &versioned("1.59.0")), Some(true));
+        assert_eq!(by_version_table(Some("1.59.0"), &versioned("1.58.1")), Some(false));
+        assert_eq!(by_version_table(Some("1.59.0"), &versioned("1.59.0-beta")), Some(true));
+        assert_eq!(by_version_table(Some("1.59.0"), &versioned("1.58.0-beta")), Some(false));
+        assert_eq!(by_version_table(Some("1.59.0"), &versioned("1.60.0-nightly")), None);
+        assert_eq!(by_version_table(Some("1.59.0"), &versioned("1.58.0-nightly")), None);
+        assert_eq!(by_version_table(Some("1.59.0"), &versioned("1.60.0-dev")), None);
+        assert_eq!(by_version_table(None, &versioned("1.60.0")), None);
+    }
+
+    #[test]
+    fn version_table_matches_compiled()
+    {
+        let features_names = vec![
+            "const_slice_first",
+            "inner_deref",
+            "iter_zip",
+            "map_first_last",
+            "question_mark",
+            "raw_ref_macros",
+            "slice_fill",
+            "try_reserve",
+            "unstable_features",
+        ];
+        let (mut compiled, _out_dir_1) =
+            CfgRustFeatures::for_test_with_dir("unittest-lib-version_table-compiled").unwrap();
+        compiled.skip_known_stable = false;
+        let (mut table, _out_dir_2) =
+            CfgRustFeatures::for_test_with_dir("unittest-lib-version_table-table").unwrap();
+        table.prefer_version_table = true;
+
+        let compiled = compiled.probe_multiple_detailed(features_names.clone()).unwrap();
+        for (name, outcome) in &compiled {
+            assert!(outcome.method != ProbeMethod::VersionTable, "{}", name);
+        }
+        let compiled: EnabledFeatures<&str> = compiled
+            .into_iter()
+            .map(|(name, outcome)| {
+                (name, if outcome.enabled { Some(outcome.categories) } else { None })
+            })
+            .collect();
+        assert_eq!(compiled, table.probe_multiple(features_names).unwrap());
+    }
+
+    #[test]
+    fn panic_abort()
+    {
+        use std::collections::HashSet;
+        use std::env;
+        use std::iter::FromIterator;
+
+        const VAR: &'static str = "CARGO_CFG_PANIC";
+        let features_names = || vec!["panic_abort"];
+        let (cfg_rust_features, _out_dir) =
+            CfgRustFeatures::for_test_with_dir("unittest-lib-panic_abort").unwrap();
+
+        // Both cases are in this one test, because tests run in parallel and the environment is
+        // shared.
+        env::set_var(VAR, "abort");
+        let enabled_features = cfg_rust_features.emit_multiple(features_names()).unwrap();
+        assert_eq!(enabled_features["panic_abort"], Some(HashSet::from_iter(vec!["comp"])));
+
+        env::set_var(VAR, "unwind");
+        let enabled_features = cfg_rust_features.emit_multiple(features_names()).unwrap();
+        assert_eq!(enabled_features["panic_abort"], None);
+
+        env::remove_var(VAR);
+    }
+
+    #[test]
+    fn target_has_atomic()
+    {
+        use std::collections::HashSet;
+        use std::env;
+        use std::iter::FromIterator;
+
+        const VAR: &'static str = "CARGO_CFG_TARGET_HAS_ATOMIC";
+        let features_names = || vec!["target_has_atomic"];
+        let (cfg_rust_features, _out_dir) =
+            CfgRustFeatures::for_test_with_dir("unittest-lib-target_has_atomic").unwrap();
+
+        // Both cases are in this one test, because tests run in parallel and the environment is
+        // shared.
+        env::set_var(VAR, "8,16,32,64,ptr");
+        let enabled_features = cfg_rust_features.emit_multiple(features_names()).unwrap();
+        assert_eq!(enabled_features["target_has_atomic"], Some(HashSet::from_iter(vec!["comp"])));
+
+        env::remove_var(VAR);
+        let enabled_features = cfg_rust_features.emit_multiple(features_names()).unwrap();
+        assert_eq!(enabled_features["target_has_atomic"], None);
+    }
+
+    #[test]
+    fn target_abi()
+    {
+        use std::env;
+
+        const VAR: &'static str = "CARGO_CFG_TARGET_ABI";
+        let (cfg_rust_features, _out_dir) =
+            CfgRustFeatures::for_test_with_dir("unittest-lib-target_abi").unwrap();
+        let enabled = || {
+            cfg_rust_features.emit_multiple(vec!["target_abi"]).unwrap()["target_abi"].is_some()
+        };
+
+        env::set_var(VAR, "eabihf");
+        assert!(enabled());
+        assert_eq!(CfgRustFeatures::emitted_names("target_abi"),
+                   vec!["target_abi", "target_abi_eabihf"]);
+
+        env::set_var(VAR, "sim-v2.0");
+        assert!(enabled());
+        assert_eq!(CfgRustFeatures::emitted_names("target_abi"),
+                   vec!["target_abi", "target_abi_sim_v2_0"]);
+
+        env::set_var(VAR, "");
+        assert!(!enabled());
+
+        env::remove_var(VAR);
+        assert!(!enabled());
+        assert_eq!(CfgRustFeatures::emitted_names("target_abi"), vec!["target_abi"]);
+        assert_eq!(CfgRustFeatures::emitted_names("rust1"), vec!["rust1"]);
+    }
+
+    #[test]
+    fn wasm()
+    {
+        use std::env;
+
+        const FAMILY: &'static str = "CARGO_CFG_TARGET_FAMILY";
+        const ARCH: &'static str = "CARGO_CFG_TARGET_ARCH";
+        let (cfg_rust_features, _out_dir) =
+            CfgRustFeatures::for_test_with_dir("unittest-lib-wasm").unwrap();
+        let enabled = || cfg_rust_features.emit_multiple(vec!["wasm"]).unwrap()["wasm"].is_some();
+
+        env::set_var(FAMILY, "wasm");
+        env::set_var(ARCH, "wasm32");
+        assert!(enabled());
+
+        env::remove_var(FAMILY);
+        assert!(enabled());
+
+        env::set_var(ARCH, "wasm64");
+        assert!(enabled());
+
+        env::set_var(FAMILY, "unix");
+        env::set_var(ARCH, "x86_64");
+        assert!(!enabled());
+
+        env::remove_var(FAMILY);
+        env::remove_var(ARCH);
+        assert!(!enabled());
+    }
+
+    #[test]
+    fn single()
+    {
+        let (cfg_rust_features, _out_dir) =
+            CfgRustFeatures::for_test_with_dir("unittest-lib-single").unwrap();
+        let rust1 = cfg_rust_features.probe_single("rust1").unwrap();
+        assert_eq!(cfg_rust_features.emit_single_feature("rust1").unwrap(), rust1);
+        assert!(rust1.is_some());
+        assert!(cfg_rust_features.probe_single("bogusness").is_err());
+        assert!(cfg_rust_features.emit_single_feature("bogusness").is_err());
+    }
+
+    #[test]
+    fn filtered()
+    {
+        use std::collections::HashSet;
+        use std::iter::FromIterator;
+
+        let all = Some(HashSet::from_iter(vec!["comp", "lang", "lib"]));
+        let lang = Some(HashSet::from_iter(vec!["lang"]));
+        let lib_only = Some(HashSet::from_iter(vec!["lib"]));
+        assert_eq!(filter_categories(&all, &["lib"]), lib_only);
+        assert_eq!(filter_categories(&all, &["comp", "lib"]),
+                   Some(HashSet::from_iter(vec!["comp", "lib"])));
+        assert_eq!(filter_categories(&lang, &["lib"]), None);
+        assert_eq!(filter_categories(&lang, &[]), None);
+        assert_eq!(filter_categories(&None, &["lib"]), None);
+
+        // Only `rust_lib_feature` options would be emitted for a mixed list, but all are probed.
+        let features_names = vec!["rust1", "never_type", "unstable_features", "iter_zip"];
+        let (cfg_rust_features, _out_dir) =
+            CfgRustFeatures::for_test_with_dir("unittest-lib-filtered").unwrap();
+        let enabled_features =
+            cfg_rust_features.emit_multiple_filtered(features_names.clone(), &["lib"]).unwrap();
+        assert_eq!(enabled_features, cfg_rust_features.probe_multiple(features_names).unwrap());
+        for enabled in enabled_features.values() {
+            if let Some(categories) = filter_categories(enabled, &["lib"]) {
+                assert_eq!(categories, HashSet::from_iter(vec!["lib"]));
+            }
+        }
+        assert_eq!(filter_categories(&enabled_features["rust1"], &["lib"]), lib_only);
+    }
+
+    #[test]
+    fn summary()
+    {
+        use std::collections::HashSet;
+        use std::iter::FromIterator;
+
+        let mut enabled_features = EnabledFeatures::new();
+        assert_eq!(EnabledFeaturesSummary(&enabled_features).to_string(), "");
+
+        let _ = enabled_features.insert("rust1", Some(HashSet::from_iter(vec!["lib", "comp"])));
+        let _ = enabled_features.insert("never_type", None);
+        let _ = enabled_features.insert("iter_zip", Some(HashSet::from_iter(vec!["lib"])));
+        assert_eq!(
+            EnabledFeaturesSummary(&enabled_features).to_string(),
+            "iter_zip: enabled (lib)\nnever_type: not enabled\nrust1: enabled (comp, lib)"
+        );
+    }
+
+    #[test]
+    fn from_env()
+    {
+        use std::env;
+
+        let (cfg_rust_features, _out_dir) =
+            CfgRustFeatures::for_test_with_dir("unittest-lib-from_env").unwrap();
+        let var = "CFG_RUST_FEATURES_UNITTEST_FROM_ENV";
+
+        env::remove_var(var);
+        assert!(cfg_rust_features.emit_from_env(var).unwrap().is_empty());
+
+        env::set_var(var, "");
+        assert!(cfg_rust_features.emit_from_env(var).unwrap().is_empty());
+
+        env::set_var(var, " rust1,never_type\titer_zip ,, ");
+        let enabled_features = cfg_rust_features.emit_from_env(var).unwrap();
+        let mut names: Vec<&str> = enabled_features.keys().map(|k| &k[..]).collect();
+        names.sort();
+        assert_eq!(names, ["iter_zip", "never_type", "rust1"]);
+        assert!(enabled_features["rust1"].is_some());
+
+        env::set_var(var, "rust1 bogus_unsupported_feature");
+        assert!(cfg_rust_features.emit_from_env(var).is_err());
+
+        env::remove_var(var);
+    }
+
+    #[test]
+    fn from_file()
+    {
+        use std::fs::File;
+        use std::io::Write;
+
+        let (cfg_rust_features, out_dir) =
+            CfgRustFeatures::for_test_with_dir("unittest-lib-from_file").unwrap();
+        let path = AsRef::<Path>::as_ref(&out_dir).join("features.txt");
+
+        let error = cfg_rust_features.emit_from_file(&path).unwrap_err();
+        assert!(error.to_string().contains("features.txt"));
+
+        File::create(&path)
+            .unwrap()
+            .write_all(b"# Shared features.\n\nrust1\n  never_type  # Trailing comment.\n\n")
+            .unwrap();
+        let enabled_features = cfg_rust_features.emit_from_file(&path).unwrap();
+        let mut names: Vec<&str> = enabled_features.keys().map(|k| &k[..]).collect();
+        names.sort();
+        assert_eq!(names, ["never_type", "rust1"]);
+        assert!(enabled_features["rust1"].is_some());
+    }
+
+    #[test]
+    fn detailed()
+    {
+        use std::collections::HashSet;
+        use std::iter::FromIterator;
+
+        let features_names = vec!["rust1", "unstable_features", "question_mark"];
+        let (cfg_rust_features, _out_dir) =
+            CfgRustFeatures::for_test_with_dir("unittest-lib-detailed").unwrap();
+        let outcomes = cfg_rust_features.probe_multiple_detailed(features_names.clone()).unwrap();
+        let enabled_features = cfg_rust_features.probe_multiple(features_names).unwrap();
+
+        assert_eq!(outcomes["rust1"].method, ProbeMethod::AlwaysEnabled);
+        assert_eq!(outcomes["unstable_features"].method, ProbeMethod::Channel);
+        assert!([ProbeMethod::Expr, ProbeMethod::VersionTable]
+            .contains(&outcomes["question_mark"].method));
+        assert_eq!(outcomes["question_mark"].categories, HashSet::from_iter(vec!["lang"]));
+        for (name, outcome) in &outcomes {
+            assert_eq!(outcome.enabled, enabled_features[name].is_some());
+        }
+    }
+
     #[test]
     fn generic()
     {
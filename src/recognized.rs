@@ -12,26 +12,65 @@ pub struct Feature
     pub name:       &'static str,
     pub categories: &'static [FeatureCategory],
     pub probe:      Probe,
+    /// The version of Rust in which the feature was stabilized, if it has been and if known.
+    pub stabilized: Option<&'static str>,
 }
 
 /// How to test whether a `rustc` version provides a feature.
 ///
 /// (Actually private to the crate, not part of public API.  Is only `pub` for old Rust versions.)
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
 pub enum Probe
 {
     Expr(&'static str),
     Type(&'static str),
     Path(&'static str),
+    /// Each is tried, in order, until one is enabled.  For when the form of a feature has varied.
+    Fallback(&'static [Probe]),
+    /// A `CARGO_CFG_*` environment variable, as set by Cargo for build scripts, that describes a
+    /// `cfg` option of the build target, and, if given, a value that it must have.  Without a
+    /// value, it must be non-empty.
+    CargoCfg(&'static str, Option<&'static str>),
+    /// A `CARGO_CFG_*` environment variable, like with `CargoCfg`, that must be non-empty, and
+    /// whose value is also emitted as a tag of the feature name (e.g. `target_abi_eabihf`).
+    CargoCfgTagged(&'static str),
+    /// The contained probe, but compiled as the given edition of Rust (e.g. `"2021"`), instead
+    /// of the default.  For features whose availability depends on the edition.
+    Edition(&'static str, &'static Probe),
+    /// The contained probe, but compiled as a `#![no_std]` crate, instead of with `std`.  For
+    /// features whose availability in `core` matters.
+    NoStd(&'static Probe),
     AlwaysEnabled,
     UnstableFeatures,
+    DevCompiler,
 }
 
+/// The wrapper type for `Sync`-ness via exclusive access.  Its older name, `Exclusive`, is not
+/// probed, because its API differed (e.g. `get_mut` instead of `as_mut`), and so the feature
+/// could not be used the same way with both.
+const SYNC_VIEW: Probe =
+    Probe::Expr("{ let mut e = std::sync::SyncView::new(0u8); let _: &mut u8 = e.as_mut(); }");
+
 /// The definition of which features are recognized by this crate.
 ///
 /// Invariant: Must always be sorted by name.  Keep this in mind when making changes to it.  There
 /// is a unit-test that checks this.
 const DEFINITION: &'static [Feature] = &[
+    Feature {
+        name:       "allocator_api",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ fn f<A: std::alloc::Allocator>(a: A) -> A { a }
+               let _ = Vec::<u8, std::alloc::Global>::new_in(f(std::alloc::Global)); }",
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "anonymous_pipe",
+        categories: &["lib"],
+        probe:      Probe::Path("std::io::pipe"),
+        stabilized: Some("1.87.0"),
+    },
     Feature {
         name:       "arbitrary_self_types",
         categories: &["lang"],
@@ -48,57 +87,757 @@ const DEFINITION: &'static [Feature] = &[
                    Wrap(Thing).m()
                }"#,
         ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "atomic_from_mut",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let mut x = 0u32; let _ = std::sync::atomic::AtomicU32::from_mut(&mut x); }",
+        ),
+        // Only provided for targets where `u32` and `AtomicU32` have the same alignment, and so
+        // must always be probed even if it becomes stable.
+        stabilized: None,
+    },
+    Feature {
+        name:       "available_parallelism",
+        categories: &["lib"],
+        probe:      Probe::Path("std::thread::available_parallelism"),
+        stabilized: Some("1.59.0"),
+    },
+    Feature {
+        name:       "backtrace",
+        categories: &["lib"],
+        probe:      Probe::Expr("std::backtrace::Backtrace::capture()"),
+        stabilized: Some("1.65.0"),
+    },
+    Feature {
+        name:       "binary_heap_drain_sorted",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let mut h = std::collections::BinaryHeap::from(vec![3, 1, 2]);
+               let _: Vec<_> = h.drain_sorted().collect(); }",
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "binary_heap_into_iter_sorted",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "std::collections::BinaryHeap::from(vec![1u8]).into_iter_sorted()",
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "black_box",
+        categories: &["lib"],
+        probe:      Probe::Path("std::hint::black_box"),
+        stabilized: Some("1.66.0"),
+    },
+    Feature {
+        name:       "btree_cursors",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let m = std::collections::BTreeMap::<u8, u8>::new();
+               let c = m.lower_bound(std::ops::Bound::Unbounded);
+               let _ = c.peek_next(); }",
+        ),
+        stabilized: None,
     },
     Feature {
         name:       "cfg_version",
         categories: &["lang"],
         probe:      Probe::Expr(r#"{ #[cfg(version("1.0"))] struct X; X }"#),
+        stabilized: None,
+    },
+    Feature {
+        name:       "closure_captures",
+        categories: &["lang"],
+        // Disjoint capture of fields.  Only with the edition in which it became the rule.
+        probe:      Probe::Edition(
+            "2021",
+            &Probe::Expr(
+                "{ struct S { a: i32, b: i32 }
+                   let mut s = S { a: 0, b: 0 };
+                   let mut c = || s.a += 1;
+                   let _ = &mut s.b;
+                   c(); }",
+            ),
+        ),
+        stabilized: Some("1.56.0"),
+    },
+    Feature {
+        name:       "cmp_minmax",
+        categories: &["lib"],
+        probe:      Probe::Path("std::cmp::minmax"),
+        stabilized: None,
+    },
+    Feature {
+        name:       "const_assert",
+        categories: &["lang"],
+        // In practice, this is enabled by the same versions as `const_panic`, because the
+        // assertion macros expand to panicking with a literal message.
+        probe:      Probe::Expr(
+            "{ const fn f(x: u8) -> u8 { assert!(x != 0); debug_assert!(x != 0); x }
+               let _ = f(1); }",
+        ),
+        stabilized: Some("1.57.0"),
+    },
+    Feature {
+        name:       "const_cstr_methods",
+        categories: &["lib"],
+        // Means `const` `to_bytes` (and `to_bytes_with_nul`), not the later `count_bytes`.
+        probe:      Probe::Expr(
+            r#"{ const C: &std::ffi::CStr =
+                     unsafe { std::ffi::CStr::from_bytes_with_nul_unchecked(b"a\0") };
+                 const B: &[u8] = C.to_bytes();
+                 let _ = B; }"#,
+        ),
+        stabilized: Some("1.72.0"),
+    },
+    Feature {
+        name:       "const_fn_float_arithmetic",
+        categories: &["lang"],
+        probe:      Probe::Expr("{ const fn f() -> f64 { 1.0 + 2.0 } let _ = f(); }"),
+        stabilized: Some("1.82.0"),
+    },
+    Feature {
+        name:       "const_let_else",
+        categories: &["lang"],
+        // In practice, this is enabled by the same versions as `let`-`else` in general, because
+        // panicking in `const fn` was stabilized before that.
+        probe:      Probe::Expr(
+            "{ const fn f(x: Option<u8>) -> u8 { let Some(y) = x else { panic!() }; y }
+               let _ = f(Some(1)); }",
+        ),
+        stabilized: Some("1.65.0"),
+    },
+    Feature {
+        name:       "const_panic",
+        categories: &["lang"],
+        probe:      Probe::Expr(
+            r#"{ const fn f(b: bool) -> i32 { if b { panic!("x") } else { 0 } }
+                 let _ = f(false); }"#,
+        ),
+        stabilized: Some("1.57.0"),
+    },
+    Feature {
+        name:       "const_slice_first",
+        categories: &["lib"],
+        probe:      Probe::Expr("{ const X: Option<&i32> = [1, 2, 3].first(); let _ = X; }"),
+        stabilized: Some("1.56.0"),
+    },
+    Feature {
+        name:       "core_net",
+        categories: &["lib"],
+        probe:      Probe::NoStd(&Probe::Path("core::net::Ipv4Addr")),
+        stabilized: Some("1.77.0"),
     },
     Feature {
         name:       "destructuring_assignment",
         categories: &["lang"],
         probe:      Probe::Expr("{ let (_a, _b); (_a, _b) = (1, 2); }"),
+        stabilized: Some("1.59.0"),
+    },
+    Feature {
+        name:       "dev_compiler",
+        categories: &["comp"],
+        probe:      Probe::DevCompiler,
+        stabilized: None,
+    },
+    Feature {
+        name:       "diagnostic_namespace",
+        categories: &["lang"],
+        // Unknown attributes in the `diagnostic` namespace are (intentionally) only warned about,
+        // but the namespace itself is an error when not supported, and so this well-known
+        // attribute is used.
+        probe:      Probe::Expr(
+            r#"{ #[diagnostic::on_unimplemented(message = "m")] trait T {} }"#,
+        ),
+        stabilized: Some("1.78.0"),
+    },
+    Feature {
+        name:       "div_duration",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let d = std::time::Duration::from_secs(2); d.div_duration_f64(d / 2) }",
+        ),
+        stabilized: Some("1.80.0"),
+    },
+    Feature {
+        name:       "duration_millis_float",
+        categories: &["lib"],
+        probe:      Probe::Expr("std::time::Duration::from_secs(1).as_millis_f64()"),
+        stabilized: None,
+    },
+    Feature {
+        name:       "entry_insert",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let mut m = std::collections::HashMap::new();
+               let _ = m.entry(1).insert_entry(2); }",
+        ),
+        stabilized: Some("1.83.0"),
+    },
+    Feature {
+        name:       "error_generic_member_access",
+        categories: &["lib"],
+        // Via `core`, which has the whole surface, so that `no_std` error libraries can also rely
+        // on this.  (Distinct from `error_in_core`, which is only the trait itself.)
+        probe:      Probe::Expr(
+            "{ #[derive(Debug)]
+               struct E;
+               impl std::fmt::Display for E {
+                   fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { Ok(()) }
+               }
+               impl core::error::Error for E {
+                   fn provide<'a>(&'a self, request: &mut core::error::Request<'a>) {
+                       request.provide_ref::<str>(\"member\");
+                   }
+               }
+               let _ = core::error::request_ref::<str>(&E as &core::error::Error); }",
+        ),
+        stabilized: None,
     },
     Feature {
         name:       "error_in_core",
         categories: &["lib"],
         probe:      Probe::Expr("{ let _: &core::error::Error; }"),
+        stabilized: Some("1.81.0"),
+    },
+    Feature {
+        name:       "error_reporter",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "std::error::Report::new(std::fmt::Error).pretty(true).to_string()",
+        ),
+        stabilized: None,
+    },
+    Feature {
+        // The same as `sync_exclusive`, but named like the gate of the unstable feature.
+        name:       "exclusive_wrapper",
+        categories: &["lib"],
+        probe:      SYNC_VIEW,
+        stabilized: None,
+    },
+    Feature {
+        name:       "exit_status_error",
+        categories: &["lib"],
+        // Without spawning a process.
+        probe:      Probe::Expr(
+            "|s: std::process::ExitStatus| -> Result<(), std::process::ExitStatusError> {
+                 s.exit_ok()
+             }",
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "extract_if",
+        categories: &["lib"],
+        // The stabilized form, which takes a range.  The unstable forms before that did not, and
+        // the earlier `drain_filter` is not recognized.
+        probe:      Probe::Expr(
+            "{ let mut v = vec![1, 2];
+               let _: Vec<i32> = v.extract_if(.., |x| *x == 1).collect(); }",
+        ),
+        stabilized: Some("1.87.0"),
+    },
+    Feature {
+        name:       "generic_nonzero",
+        categories: &["lib"],
+        probe:      Probe::Type("core::num::NonZero<u32>"),
+        stabilized: Some("1.79.0"),
+    },
+    Feature {
+        name:       "hash_raw_entry",
+        categories: &["lib"],
+        // Retired: the API was removed from `std` (it remains only in the `hashbrown` crate), and
+        // so this is only enabled by the older nightly versions that still provide it.
+        probe:      Probe::Expr(
+            "{ let mut m = std::collections::HashMap::<u8, u8>::new();
+               let _ = m.raw_entry_mut().from_key(&1); }",
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "hash_set_entry",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let mut s = std::collections::HashSet::<String>::new();
+               let _ = s.get_or_insert_with(\"a\", |k| k.to_string()); }",
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "impl_trait_in_assoc_type",
+        categories: &["lang"],
+        probe:      Probe::Expr(
+            "{ trait Tr { type Assoc; fn f(&self) -> Self::Assoc; }
+               struct S;
+               impl Tr for S { type Assoc = impl Sized; fn f(&self) -> Self::Assoc { () } } }",
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "inline_const_pat",
+        categories: &["lang"],
+        // Retired: the syntax was removed from the language, and so this is only enabled by the
+        // older nightly versions that still accept it.
+        probe:      Probe::Expr("match 1u8 { const { 1 } => (), _ => () }"),
+        stabilized: None,
     },
     Feature {
         name:       "inner_deref",
         categories: &["lib"],
         probe:      Probe::Expr("Ok::<_, ()>(vec![1]).as_deref()"),
+        stabilized: Some("1.47.0"),
+    },
+    Feature {
+        name:       "io_error_more",
+        categories: &["lib"],
+        probe:      Probe::Expr("std::io::ErrorKind::NotADirectory"),
+        // Only some of the variants were stabilized, including the probed one, and others remain
+        // unstable under the same feature gate.
+        stabilized: Some("1.83.0"),
+    },
+    Feature {
+        name:       "io_error_other",
+        categories: &["lib"],
+        probe:      Probe::Expr(r#"std::io::Error::other("x")"#),
+        stabilized: Some("1.74.0"),
+    },
+    Feature {
+        name:       "io_safety",
+        categories: &["lib"],
+        probe:      Probe::Fallback(&[
+            Probe::Path("std::os::fd::OwnedFd"),
+            // Before the `std::os::fd` module was stabilized (in 1.66).
+            Probe::Path("std::os::unix::io::OwnedFd"),
+        ]),
+        // Stable since 1.63.0, but only on some platforms, and so must always be probed.
+        stabilized: None,
+    },
+    Feature {
+        name:       "is_none_or",
+        categories: &["lib"],
+        probe:      Probe::Expr("None::<i32>.is_none_or(|x| x > 0)"),
+        stabilized: Some("1.82.0"),
+    },
+    Feature {
+        name:       "iter_advance_by",
+        categories: &["lib"],
+        // The error type has changed, and so it is not used.
+        probe:      Probe::Expr("{ let mut r = 0..3; let _ = r.advance_by(2).is_ok(); }"),
+        stabilized: None,
+    },
+    Feature {
+        name:       "iter_array_chunks",
+        categories: &["lib"],
+        // The `Iterator` adapter, which is distinct from the slice methods of `slice_as_chunks`.
+        probe:      Probe::Expr("{ let mut c = (0..6).array_chunks::<2>(); let _ = c.next(); }"),
+        stabilized: None,
+    },
+    Feature {
+        name:       "iter_collect_into",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let mut v = vec![0u8]; let _ = (1..3).collect_into(&mut v); }",
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "iter_intersperse",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            r#"{ let _: String = vec!["a", "b"].into_iter().intersperse(", ").collect(); }"#,
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "iter_map_windows",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let _: Vec<i32> = (0..4).map_windows(|w: &[i32; 2]| w[0] + w[1]).collect(); }",
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "iter_next_chunk",
+        categories: &["lib"],
+        probe:      Probe::Expr("(0u8..4).next_chunk::<2>()"),
+        stabilized: None,
+    },
+    Feature {
+        name:       "iter_partition_in_place",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let mut a = [1, 2, 3]; a.iter_mut().partition_in_place(|&x| x % 2 == 0) }",
+        ),
+        stabilized: None,
     },
     Feature {
         name:       "iter_zip",
         categories: &["lib"],
         probe:      Probe::Path("std::iter::zip"),
+        stabilized: Some("1.59.0"),
+    },
+    Feature {
+        name:       "iterator_try_collect",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let _r: Result<Vec<i32>, ()> = vec![Ok(1), Ok(2)].into_iter().try_collect(); }",
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "linked_list_cursors",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let mut l = std::collections::LinkedList::<u8>::new();
+               let mut c = l.cursor_front_mut();
+               c.move_next(); }",
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "map_first_last",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let mut m = std::collections::BTreeMap::<u8, u8>::new();
+               let _ = m.first_key_value(); let _ = m.pop_first(); }",
+        ),
+        stabilized: Some("1.66.0"),
+    },
+    Feature {
+        name:       "maybe_uninit_slice",
+        categories: &["lib"],
+        probe:      Probe::Fallback(&[
+            Probe::Expr(
+                "{ let a = [std::mem::MaybeUninit::new(1u8)];
+                   let _ = unsafe { a.assume_init_ref() }; }",
+            ),
+            Probe::Expr(
+                "{ let a = [std::mem::MaybeUninit::new(1u8)];
+                   let _ = unsafe { std::mem::MaybeUninit::slice_assume_init_ref(&a) }; }",
+            ),
+        ]),
+        stabilized: Some("1.93.0"),
+    },
+    Feature {
+        name:       "maybe_uninit_uninit_array",
+        categories: &["lib"],
+        // Retired: the API was removed from `std`, and so this is only enabled by the older
+        // nightly versions that still provide it.
+        probe:      Probe::Expr("std::mem::MaybeUninit::<u8>::uninit_array::<4>()"),
+        stabilized: None,
+    },
+    Feature {
+        name:       "mpmc_channel",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let (tx, rx) = std::sync::mpmc::channel(); tx.send(1u8).unwrap(); rx.recv() }",
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "mutex_unpoison",
+        categories: &["lib"],
+        probe:      Probe::Expr("std::sync::Mutex::new(()).clear_poison()"),
+        stabilized: Some("1.77.0"),
+    },
+    Feature {
+        name:       "never_type",
+        categories: &["lang"],
+        probe:      Probe::Type("!"),
+        stabilized: None,
+    },
+    Feature {
+        name:       "new_uninit",
+        categories: &["lib"],
+        // Only the `new_uninit` and `new_uninit_slice` parts, not the `new_zeroed` parts which
+        // were split out to remain unstable.
+        probe:      Probe::Expr(
+            "{ let _ = Box::<u32>::new_uninit();
+               let _ = std::rc::Rc::<[u8]>::new_uninit_slice(4); }",
+        ),
+        stabilized: Some("1.82.0"),
+    },
+    Feature {
+        name:       "option_get_or_insert_default",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let mut o = None::<Vec<u8>>; o.get_or_insert_default().push(1); }",
+        ),
+        stabilized: Some("1.83.0"),
+    },
+    Feature {
+        name:       "option_take_if",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let mut o = Some(1); let _ = o.take_if(|v: &mut i32| *v == 1); }",
+        ),
+        stabilized: Some("1.80.0"),
+    },
+    Feature {
+        name:       "panic_abort",
+        categories: &["comp"],
+        probe:      Probe::CargoCfg("CARGO_CFG_PANIC", Some("abort")),
+        stabilized: None,
+    },
+    Feature {
+        name:       "panic_info_message",
+        categories: &["lib"],
+        // The stable form, where `PanicInfo` is only for `#[panic_handler]` and hooks are given a
+        // `PanicHookInfo` instead.
+        probe:      Probe::Expr(
+            "|info: &core::panic::PanicInfo| { let _ = info.message().as_str(); }",
+        ),
+        stabilized: Some("1.81.0"),
+    },
+    Feature {
+        name:       "panic_update_hook",
+        categories: &["lib"],
+        probe:      Probe::Expr("std::panic::update_hook(|prev, info| prev(info))"),
+        stabilized: None,
+    },
+    Feature {
+        name:       "pattern",
+        categories: &["lib"],
+        probe:      Probe::Fallback(&[
+            Probe::Expr(
+                r#"{ fn f<P: std::str::pattern::Pattern>(s: &str, p: P) -> bool { s.contains(p) }
+                     f("ab", 'b') }"#,
+            ),
+            // Before its lifetime parameter was removed.
+            Probe::Expr(
+                r#"{ fn f<'a, P: std::str::pattern::Pattern<'a>>(s: &'a str, p: P) -> bool {
+                         s.contains(p)
+                     }
+                     f("ab", 'b') }"#,
+            ),
+        ]),
+        stabilized: None,
+    },
+    Feature {
+        name:       "portable_simd",
+        categories: &["lib"],
+        probe:      Probe::Path("std::simd::Simd"),
+        stabilized: None,
+    },
+    Feature {
+        name:       "process_exitcode",
+        categories: &["lib"],
+        probe:      Probe::Expr("std::process::ExitCode::SUCCESS"),
+        stabilized: Some("1.61.0"),
     },
-    Feature { name: "never_type", categories: &["lang"], probe: Probe::Type("!") },
     Feature {
         name:       "question_mark",
         categories: &["lang"],
         probe:      Probe::Expr("|| -> Result<(), ()> { Err(())? }"),
+        stabilized: Some("1.13.0"),
+    },
+    Feature {
+        name:       "raw_ref_macros",
+        categories: &["lib"],
+        probe:      Probe::Expr("{ let x = 0u8; let _ = std::ptr::addr_of!(x); }"),
+        stabilized: Some("1.51.0"),
+    },
+    Feature {
+        name:       "read_buf",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            r#"{ let mut m = [std::mem::MaybeUninit::<u8>::uninit(); 4];
+                 let mut b = std::io::BorrowedBuf::from(&mut m[..]);
+                 std::io::Read::read_buf(&mut &b"ab"[..], b.unfilled()) }"#,
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "result_flattening",
+        categories: &["lib"],
+        probe:      Probe::Expr("Ok::<Result<i32, ()>, ()>(Ok(1)).flatten()"),
+        stabilized: Some("1.89.0"),
     },
     Feature {
         name:       "rust1",
         categories: &["comp", "lang", "lib"],
         probe:      Probe::AlwaysEnabled,
+        stabilized: None,
+    },
+    Feature {
+        name:       "scoped_threads",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let x = 1; std::thread::scope(|s| { let _ = s.spawn(|| x + 1); }); }",
+        ),
+        stabilized: Some("1.63.0"),
+    },
+    Feature {
+        name:       "slice_as_chunks",
+        categories: &["lib"],
+        // It has always returned the remainder too, and so no fallback for another form is
+        // needed.
+        probe:      Probe::Expr("{ let (_chunks, _rem) = [1, 2, 3, 4, 5].as_chunks::<2>(); }"),
+        stabilized: Some("1.88.0"),
+    },
+    Feature {
+        name:       "slice_fill",
+        categories: &["lib"],
+        probe:      Probe::Expr("{ let mut a = [0u8; 3]; a.fill(1); }"),
+        stabilized: Some("1.50.0"),
+    },
+    Feature {
+        name:       "slice_fill_with",
+        categories: &["lib"],
+        probe:      Probe::Expr("{ let mut a = [0u8; 3]; a.fill_with(Default::default); }"),
+        stabilized: Some("1.51.0"),
+    },
+    Feature {
+        name:       "slice_first_last_chunk",
+        categories: &["lib"],
+        probe:      Probe::Expr("[0u8; 8].split_first_chunk::<4>()"),
+        stabilized: Some("1.77.0"),
+    },
+    Feature {
+        name:       "slice_take",
+        categories: &["lib"],
+        probe:      Probe::Fallback(&[
+            Probe::Expr("{ let mut s: &[u8] = &[1]; let _ = s.split_off_first(); }"),
+            // Before `take_first` and `take` were renamed to `split_off_first` and `split_off`.
+            Probe::Expr("{ let mut s: &[u8] = &[1]; let _ = s.take_first(); }"),
+        ]),
+        stabilized: Some("1.87.0"),
     },
     Feature {
         name:       "step_trait",
         categories: &["lib"],
         probe:      Probe::Path("std::iter::Step"),
+        stabilized: None,
+    },
+    Feature {
+        name:       "sync_exclusive",
+        categories: &["lib"],
+        probe:      SYNC_VIEW,
+        stabilized: None,
+    },
+    Feature {
+        name:       "target_abi",
+        categories: &["comp"],
+        probe:      Probe::CargoCfgTagged("CARGO_CFG_TARGET_ABI"),
+        stabilized: None,
+    },
+    Feature {
+        name:       "target_has_atomic",
+        categories: &["comp"],
+        probe:      Probe::CargoCfg("CARGO_CFG_TARGET_HAS_ATOMIC", None),
+        stabilized: None,
+    },
+    Feature {
+        name:       "thread_sleep_until",
+        categories: &["lib"],
+        probe:      Probe::Expr("std::thread::sleep_until(std::time::Instant::now())"),
+        stabilized: None,
+    },
+    Feature {
+        name:       "try_find",
+        categories: &["lib"],
+        probe:      Probe::Expr("(0..3).try_find(|&x| Ok::<bool, ()>(x == 1))"),
+        stabilized: None,
+    },
+    Feature {
+        name:       "try_reserve",
+        categories: &["lib"],
+        probe:      Probe::Expr("Vec::<u8>::new().try_reserve(1)"),
+        stabilized: Some("1.57.0"),
+    },
+    Feature {
+        name:       "try_trait_v2",
+        // The traits are library items, but implementing them affects the `?` operator.
+        categories: &["lang", "lib"],
+        probe:      Probe::Expr(
+            "{ use std::convert::Infallible;
+               use std::ops::{ControlFlow, FromResidual, Try};
+               struct T(Option<i32>);
+               impl Try for T {
+                   type Output = i32;
+                   type Residual = Option<Infallible>;
+                   fn from_output(o: i32) -> Self { T(Some(o)) }
+                   fn branch(self) -> ControlFlow<Option<Infallible>, i32> {
+                       match self.0 {
+                           Some(o) => ControlFlow::Continue(o),
+                           None => ControlFlow::Break(None),
+                       }
+                   }
+               }
+               impl FromResidual<Option<Infallible>> for T {
+                   fn from_residual(_: Option<Infallible>) -> Self { T(None) }
+               } }",
+        ),
+        stabilized: None,
     },
     Feature {
         name:       "unstable_features",
         categories: &["comp"],
         probe:      Probe::UnstableFeatures,
+        stabilized: None,
     },
     Feature {
         name:       "unwrap_infallible",
         categories: &["lib"],
         probe:      Probe::Expr("Ok::<(), !>(()).into_ok()"),
+        stabilized: None,
+    },
+    Feature {
+        name:       "vec_into_raw_parts",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let (p, l, c) = vec![1u8].into_raw_parts();
+               let _ = unsafe { Vec::from_raw_parts(p, l, c) }; }",
+        ),
+        stabilized: Some("1.93.0"),
+    },
+    Feature {
+        name:       "vec_push_within_capacity",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let mut v = Vec::with_capacity(1); let _ = v.push_within_capacity(1u8); }",
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "vec_split_at_spare",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            "{ let mut v = Vec::<i32>::with_capacity(4); let _ = v.split_at_spare_mut(); }",
+        ),
+        stabilized: None,
+    },
+    Feature {
+        name:       "wasm",
+        categories: &["comp"],
+        probe:      Probe::Fallback(&[
+            Probe::CargoCfg("CARGO_CFG_TARGET_FAMILY", Some("wasm")),
+            // Older versions did not set the "wasm" family.
+            Probe::CargoCfg("CARGO_CFG_TARGET_ARCH", Some("wasm32")),
+            Probe::CargoCfg("CARGO_CFG_TARGET_ARCH", Some("wasm64")),
+        ]),
+        stabilized: None,
+    },
+    Feature {
+        name:       "windows_io_safety",
+        categories: &["lib"],
+        probe:      Probe::Path("std::os::windows::io::OwnedHandle"),
+        // Stable since 1.63.0, but only on Windows, and so must always be probed.
+        stabilized: None,
+    },
+    Feature {
+        name:       "write_all_vectored",
+        categories: &["lib"],
+        probe:      Probe::Expr(
+            r#"{ let mut s = [std::io::IoSlice::new(b"a"), std::io::IoSlice::new(b"b")];
+                 std::io::Write::write_all_vectored(&mut Vec::new(), &mut s) }"#,
+        ),
+        stabilized: None,
     },
 ];
 
@@ -117,7 +856,7 @@ pub fn get(feature_name: &str) -> Option<&'static Feature>
 #[cfg(test)]
 mod tests
 {
-    use super::{Feature, DEFINITION};
+    use super::{Feature, Probe, DEFINITION};
 
     fn sorted() -> Vec<Feature>
     {
@@ -139,4 +878,44 @@ mod tests
     {
         assert_eq!(DEFINITION, &*sorted());
     }
+
+    #[test]
+    fn equal_probes_hash_equally()
+    {
+        use std::hash::{Hash, Hasher, SipHasher};
+
+        fn hash(probe: &Probe) -> u64
+        {
+            let mut hasher = SipHasher::new();
+            probe.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        const ALTERNATIVES: &'static [Probe] = &[Probe::Path("std::u8"), Probe::AlwaysEnabled];
+        let probes = [
+            Probe::Expr("1"),
+            Probe::CargoCfg("CARGO_CFG_PANIC", Some("abort")),
+            Probe::Fallback(ALTERNATIVES),
+        ];
+        for probe in &probes {
+            let copy = *probe;
+            assert_eq!(*probe, copy);
+            assert_eq!(hash(probe), hash(&copy));
+        }
+        for feature in DEFINITION {
+            assert_eq!(hash(&feature.probe), hash(&feature.probe.clone()));
+        }
+    }
+
+    #[test]
+    fn stabilized_versions_parse()
+    {
+        use version_check::Version;
+
+        for feature in DEFINITION {
+            if let Some(version) = feature.stabilized {
+                assert!(Version::parse(version).is_some(), "{}", feature.name);
+            }
+        }
+    }
 }
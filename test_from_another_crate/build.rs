@@ -4,18 +4,100 @@ extern crate cfg_rust_features;
 fn main()
 {
     emit!(vec![
+        "allocator_api",
+        "anonymous_pipe",
         "arbitrary_self_types",
+        "atomic_from_mut",
+        "available_parallelism",
+        "backtrace",
+        "binary_heap_drain_sorted",
+        "binary_heap_into_iter_sorted",
+        "black_box",
+        "btree_cursors",
         "cfg_version",
+        "closure_captures",
+        "cmp_minmax",
+        "const_assert",
+        "const_cstr_methods",
+        "const_fn_float_arithmetic",
+        "const_let_else",
+        "const_panic",
+        "const_slice_first",
+        "core_net",
         "destructuring_assignment",
+        "dev_compiler",
+        "diagnostic_namespace",
+        "div_duration",
+        "duration_millis_float",
+        "entry_insert",
+        "error_generic_member_access",
         "error_in_core",
+        "error_reporter",
+        "exclusive_wrapper",
+        "exit_status_error",
+        "extract_if",
+        "generic_nonzero",
+        "hash_raw_entry",
+        "hash_set_entry",
+        "impl_trait_in_assoc_type",
+        "inline_const_pat",
         "inner_deref",
+        "io_error_more",
+        "io_error_other",
+        "io_safety",
+        "is_none_or",
+        "iter_advance_by",
+        "iter_array_chunks",
+        "iter_collect_into",
+        "iter_intersperse",
+        "iter_map_windows",
+        "iter_next_chunk",
+        "iter_partition_in_place",
         "iter_zip",
+        "iterator_try_collect",
+        "linked_list_cursors",
+        "map_first_last",
+        "maybe_uninit_slice",
+        "maybe_uninit_uninit_array",
+        "mpmc_channel",
+        "mutex_unpoison",
         "never_type",
+        "new_uninit",
+        "option_get_or_insert_default",
+        "option_take_if",
+        "panic_abort",
+        "panic_info_message",
+        "panic_update_hook",
+        "pattern",
+        "portable_simd",
+        "process_exitcode",
         "question_mark",
+        "raw_ref_macros",
+        "read_buf",
+        "result_flattening",
         "rust1",
+        "scoped_threads",
+        "slice_as_chunks",
+        "slice_fill",
+        "slice_fill_with",
+        "slice_first_last_chunk",
+        "slice_take",
         "step_trait",
+        "sync_exclusive",
+        "target_abi",
+        "target_has_atomic",
+        "thread_sleep_until",
+        "try_find",
+        "try_reserve",
+        "try_trait_v2",
         "unstable_features",
         "unwrap_infallible",
+        "vec_into_raw_parts",
+        "vec_push_within_capacity",
+        "vec_split_at_spare",
+        "wasm",
+        "windows_io_safety",
+        "write_all_vectored",
     ])
     .unwrap();
 }
@@ -11,18 +11,103 @@
     special_dev_test = "enable-unstable-features",
     // For development testing, pretend that the recognized features have become stable.
     feature(
+        allocator_api,
+        anonymous_pipe,
         arbitrary_self_types,
+        atomic_from_mut,
+        available_parallelism,
+        backtrace,
+        bench_black_box,
+        binary_heap_drain_sorted,
+        binary_heap_into_iter_sorted,
+        btree_cursors,
         cfg_version,
+        cmp_minmax,
+        const_cstr_methods,
+        const_fn_floating_point_arithmetic,
+        const_panic,
+        core_io_borrowed_buf,
         destructuring_assignment,
+        diagnostic_namespace,
+        div_duration,
+        duration_millis_float,
+        entry_insert,
+        error_generic_member_access,
         error_in_core,
+        error_reporter,
+        exclusive_wrapper,
+        exit_status_error,
+        extract_if,
+        generic_nonzero,
+        hash_set_entry,
+        impl_trait_in_assoc_type,
         inner_deref,
+        io_error_more,
+        io_error_other,
+        io_safety,
+        is_none_or,
+        iter_advance_by,
+        iter_array_chunks,
+        iter_collect_into,
+        iter_intersperse,
+        iter_map_windows,
+        iter_next_chunk,
+        iter_partition_in_place,
         iter_zip,
+        iterator_try_collect,
+        linked_list_cursors,
+        map_first_last,
+        maybe_uninit_slice,
+        mpmc_channel,
+        mutex_unpoison,
         never_type,
+        new_uninit,
+        option_get_or_insert_default,
+        option_take_if,
+        panic_info_message,
+        panic_update_hook,
+        pattern,
+        portable_simd,
+        process_exitcode,
         question_mark,
+        raw_ref_macros,
+        read_buf,
+        result_flattening,
+        scoped_threads,
+        slice_as_chunks,
+        slice_fill,
+        slice_fill_with,
+        slice_first_last_chunk,
+        slice_take,
         step_trait,
+        thread_sleep_until,
+        try_find,
+        try_reserve,
+        try_trait_v2,
         unwrap_infallible,
+        vec_into_raw_parts,
+        vec_push_within_capacity,
+        vec_split_at_spare,
+        write_all_vectored,
     )
 )]
+// Retired features, whose gates were removed from newer versions, are only enabled when they are
+// available, so that the development testing can be done with both older and newer versions.
+#![cfg_attr(
+    all(special_dev_test = "enable-unstable-features", rust_lib_feature = "hash_raw_entry"),
+    feature(hash_raw_entry)
+)]
+#![cfg_attr(
+    all(special_dev_test = "enable-unstable-features", rust_lang_feature = "inline_const_pat"),
+    feature(inline_const_pat)
+)]
+#![cfg_attr(
+    all(
+        special_dev_test = "enable-unstable-features",
+        rust_lib_feature = "maybe_uninit_uninit_array"
+    ),
+    feature(maybe_uninit_uninit_array)
+)]
 
 // Similar to above, this uses a currently-unrecognized feature.
 #[cfg(any(rust_lib_feature = "test", rust_comp_feature = "unstable_features"))]
@@ -50,6 +135,80 @@ mod tests
         pub type F = fn() -> !;
     }
 
+    /// Serializes the tests that change the global panic hook, and the tests that panic while the
+    /// hook could be changed, so that they do not interfere.
+    #[allow(dead_code, deprecated)]
+    mod panic_hook_lock
+    {
+        use std::sync::atomic::{AtomicBool, Ordering, ATOMIC_BOOL_INIT};
+        use std::thread;
+
+        static LOCKED: AtomicBool = ATOMIC_BOOL_INIT;
+
+        pub struct Guard;
+
+        pub fn lock() -> Guard
+        {
+            while LOCKED.compare_and_swap(false, true, Ordering::Acquire) {
+                thread::yield_now();
+            }
+            Guard
+        }
+
+        impl Drop for Guard
+        {
+            fn drop(&mut self)
+            {
+                LOCKED.store(false, Ordering::Release);
+            }
+        }
+    }
+
+    #[cfg(rust_lib_feature = "allocator_api")]
+    #[test]
+    fn allocator_api()
+    {
+        use std::alloc::{AllocError, Allocator, Global, Layout};
+        use std::ptr::NonNull;
+
+        struct Forwarding;
+
+        unsafe impl Allocator for Forwarding
+        {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError>
+            {
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout)
+            {
+                Global.deallocate(ptr, layout)
+            }
+        }
+
+        let mut v = Vec::new_in(Forwarding);
+        v.push(1u8);
+        v.push(2);
+        assert_eq!(&[1, 2], &*v);
+
+        let b = Box::new_in(3u32, Forwarding);
+        assert_eq!(3, *b);
+    }
+
+    #[cfg(all(any(unix, windows), rust_lib_feature = "anonymous_pipe"))]
+    #[test]
+    fn anonymous_pipe()
+    {
+        use std::io::{self, Read, Write};
+
+        let (mut reader, mut writer) = io::pipe().unwrap();
+        writer.write_all(b"piped").unwrap();
+        drop(writer);
+        let mut s = String::new();
+        reader.read_to_string(&mut s).unwrap();
+        assert_eq!("piped", s);
+    }
+
     #[cfg(rust_lang_feature = "arbitrary_self_types")]
     #[test]
     fn arbitrary_self_types()
@@ -90,6 +249,111 @@ mod tests
         assert!(Wrap(Thing(true)).inherent_method());
     }
 
+    #[cfg(rust_lib_feature = "atomic_from_mut")]
+    #[test]
+    fn atomic_from_mut()
+    {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let mut x = 1u32;
+        {
+            let a = AtomicU32::from_mut(&mut x);
+            assert_eq!(1, a.swap(2, Ordering::Relaxed));
+            a.store(3, Ordering::Relaxed);
+        }
+        assert_eq!(3, x);
+    }
+
+    #[cfg(rust_lib_feature = "available_parallelism")]
+    #[test]
+    fn available_parallelism()
+    {
+        assert!(std::thread::available_parallelism().unwrap().get() >= 1);
+    }
+
+    #[cfg(rust_lib_feature = "backtrace")]
+    #[test]
+    fn backtrace()
+    {
+        use std::backtrace::Backtrace;
+        use std::backtrace::BacktraceStatus::{Captured, Disabled, Unsupported};
+
+        // Whichever, depending on `RUST_BACKTRACE` and the platform.
+        match Backtrace::capture().status() {
+            Unsupported | Disabled | Captured => (),
+            _ => panic!("undocumented status"),
+        }
+        assert_eq!(Disabled, Backtrace::disabled().status());
+    }
+
+    #[cfg(rust_lib_feature = "binary_heap_drain_sorted")]
+    #[test]
+    fn binary_heap_drain_sorted()
+    {
+        use std::collections::BinaryHeap;
+
+        let mut heap = BinaryHeap::from(vec![3, 1, 2]);
+        let drained: Vec<_> = heap.drain_sorted().collect();
+        assert_eq!(vec![3, 2, 1], drained);
+        assert!(heap.is_empty());
+
+        heap.extend(vec![5, 4]);
+        assert_eq!(Some(5), heap.drain_sorted().next());
+        assert!(heap.is_empty());
+    }
+
+    #[cfg(rust_lib_feature = "binary_heap_into_iter_sorted")]
+    #[test]
+    fn binary_heap_into_iter_sorted()
+    {
+        use std::cmp::Reverse;
+        use std::collections::BinaryHeap;
+
+        let heap = BinaryHeap::from(vec![3, 1, 4, 1, 5]);
+        let top: Vec<_> = heap.into_iter_sorted().take(2).collect();
+        assert_eq!(vec![5, 4], top);
+
+        let heap = BinaryHeap::from(vec![Reverse(3), Reverse(1), Reverse(4)]);
+        let bottom: Vec<_> = heap.into_iter_sorted().take(2).collect();
+        assert_eq!(vec![Reverse(1), Reverse(3)], bottom);
+    }
+
+    #[cfg(rust_lib_feature = "black_box")]
+    #[test]
+    fn black_box()
+    {
+        use std::hint::black_box;
+
+        assert_eq!(1, black_box(1));
+        assert_eq!(vec![2, 3], black_box(vec![2, 3]));
+    }
+
+    #[cfg(rust_lib_feature = "btree_cursors")]
+    #[test]
+    fn btree_cursors()
+    {
+        use std::collections::BTreeMap;
+        use std::ops::Bound;
+
+        let mut m = BTreeMap::new();
+        m.insert(1, 'a');
+        m.insert(3, 'c');
+        m.insert(5, 'e');
+
+        let mut cursor = m.lower_bound(Bound::Included(&2));
+        assert_eq!(Some((&1, &'a')), cursor.peek_prev());
+        assert_eq!(Some((&3, &'c')), cursor.next());
+        assert_eq!(Some((&5, &'e')), cursor.next());
+        assert_eq!(None, cursor.next());
+
+        let mut cursor = m.lower_bound_mut(Bound::Unbounded);
+        assert_eq!(Some((&1, &mut 'a')), cursor.next());
+        cursor.insert_after(2, 'b').unwrap();
+        assert!(cursor.insert_after(9, 'z').is_err());
+        let all: Vec<_> = m.into_iter().collect();
+        assert_eq!(vec![(1, 'a'), (2, 'b'), (3, 'c'), (5, 'e')], all);
+    }
+
     #[cfg(rust_lang_feature = "cfg_version")]
     #[test]
     fn cfg_version()
@@ -105,109 +369,1415 @@ mod tests
         shield!();
     }
 
-    #[cfg(rust_lang_feature = "destructuring_assignment")]
+    #[cfg(rust_lang_feature = "closure_captures")]
     #[test]
-    fn destructuring_assignment()
+    fn closure_captures()
     {
-        let (a, b);
-        (a, b) = (true, false);
-        assert_ne!(a, b);
+        // Nothing to exercise, because this package is of the 2015 edition and so its closures
+        // capture whole variables.  A package of the 2021 (or later) edition could rely on
+        // disjoint captures with this option.
     }
 
-    #[cfg(rust_lib_feature = "error_in_core")]
+    #[cfg(rust_lib_feature = "cmp_minmax")]
     #[test]
-    fn error_in_core()
+    fn cmp_minmax()
     {
-        let e: &core::error::Error = &std::fmt::Error;
-        assert!(e.is::<std::fmt::Error>());
+        use std::cmp::{minmax, minmax_by_key};
+
+        assert_eq!([1, 3], minmax(3, 1));
+        assert_eq!([1, 3], minmax(1, 3));
+        assert_eq!([-1, 2], minmax_by_key(2, -1, |x: &i32| x.abs()));
     }
 
-    #[cfg(rust_lib_feature = "inner_deref")]
+    #[cfg(rust_lang_feature = "const_assert")]
     #[test]
-    fn inner_deref()
+    fn const_assert()
     {
-        assert_eq!(Ok(&1), Ok::<_, ()>(Box::new(1)).as_deref());
+        // Prevent old Rust versions from erroring on the `const fn` syntax.
+        macro_rules! shield {
+            () => {
+                const fn nonzero(x: u8) -> u8
+                {
+                    assert!(x != 0, "zero");
+                    debug_assert!(x < 100);
+                    x
+                }
+                const ONE: u8 = nonzero(1);
+                assert_eq!(1, ONE);
+            };
+        }
+        shield!();
     }
 
-    #[cfg(rust_lib_feature = "iter_zip")]
+    #[cfg(rust_lib_feature = "const_cstr_methods")]
     #[test]
-    fn iter_zip()
+    fn const_cstr_methods()
     {
-        assert_eq!(vec![(1, 2)], std::iter::zip([1], [2]).collect::<Vec<_>>());
+        use std::ffi::CStr;
+
+        const HELLO: &CStr = match CStr::from_bytes_with_nul(b"hello\0") {
+            Ok(c) => c,
+            Err(_) => panic!(),
+        };
+        const BYTES: &[u8] = HELLO.to_bytes();
+        const WITH_NUL: &[u8] = HELLO.to_bytes_with_nul();
+        assert_eq!(b"hello", BYTES);
+        assert_eq!(b"hello\0", WITH_NUL);
     }
 
-    #[cfg(rust_lang_feature = "never_type")]
+    #[cfg(rust_lang_feature = "const_fn_float_arithmetic")]
     #[test]
-    fn never_type()
+    fn const_fn_float_arithmetic()
     {
-        // Prevent old Rust versions from erroring on the `!` syntax.
+        // Prevent old Rust versions from erroring on the `const fn` syntax.
         macro_rules! shield {
             () => {
-                let _: [!; 0] = [];
+                const fn mean(a: f64, b: f64) -> f64 { (a + b) / 2.0 }
+                const MEAN: f64 = mean(1.0, 2.0);
+                assert_eq!(1.5, MEAN);
             };
         }
         shield!();
     }
 
-    #[cfg(rust_lang_feature = "question_mark")]
+    #[cfg(rust_lang_feature = "const_let_else")]
     #[test]
-    fn question_mark()
+    fn const_let_else()
     {
-        // Prevent old Rust versions from erroring on the `?` syntax.
+        // Prevent old Rust versions from erroring on the `let`-`else` syntax.
         macro_rules! shield {
             () => {
-                Err(())?
+                const fn or_zero(x: Option<u8>) -> u8
+                {
+                    let Some(y) = x else { return 0 };
+                    y
+                }
+                const fn unwrapped(x: Option<u8>) -> u8
+                {
+                    let Some(y) = x else { panic!("none") };
+                    y
+                }
+                const ONE: u8 = unwrapped(Some(1));
+                const ZERO: u8 = or_zero(None);
+                assert_eq!((1, 0), (ONE, ZERO));
             };
         }
-        fn f() -> Result<(), ()>
-        {
-            shield!()
+        shield!();
+    }
+
+    #[cfg(rust_lang_feature = "const_panic")]
+    #[test]
+    fn const_panic()
+    {
+        // Prevent old Rust versions from erroring on the `const fn` syntax.
+        macro_rules! shield {
+            () => {
+                const fn checked_half(x: u32) -> u32
+                {
+                    if x % 2 != 0 {
+                        panic!("odd");
+                    }
+                    x / 2
+                }
+                const HALF: u32 = checked_half(8);
+                assert_eq!(4, HALF);
+            };
         }
-        assert_eq!(Err(()), f());
+        shield!();
     }
 
-    #[cfg(rust_comp_feature = "rust1")]
+    #[cfg(rust_lib_feature = "const_slice_first")]
     #[test]
-    fn rust1_comp() {}
+    fn const_slice_first()
+    {
+        // Prevent old Rust versions from erroring on the `const fn` syntax.
+        macro_rules! shield {
+            () => {
+                const fn first_or_zero(s: &[i32]) -> i32
+                {
+                    match s.first() {
+                        Some(&x) => x,
+                        None => 0,
+                    }
+                }
+                const FIRST: Option<&i32> = [1, 2, 3].first();
+                const EMPTY: i32 = first_or_zero(&[]);
+                assert_eq!(Some(&1), FIRST);
+                assert_eq!(0, EMPTY);
+            };
+        }
+        shield!();
+    }
 
-    #[cfg(rust_lang_feature = "rust1")]
+    #[cfg(rust_lib_feature = "core_net")]
     #[test]
-    fn rust1_lang() {}
+    fn core_net()
+    {
+        let addr = core::net::Ipv4Addr::new(127, 0, 0, 1);
+        assert!(addr.is_loopback());
+        // The same type as re-exported by `std`.
+        assert_eq!(std::net::Ipv4Addr::LOCALHOST, addr);
+    }
 
-    #[cfg(rust_lib_feature = "rust1")]
+    #[cfg(rust_lang_feature = "destructuring_assignment")]
     #[test]
-    fn rust1_lib() {}
+    fn destructuring_assignment()
+    {
+        let (a, b);
+        (a, b) = (true, false);
+        assert_ne!(a, b);
+    }
 
-    #[cfg(rust_lib_feature = "step_trait")]
+    #[cfg(rust_comp_feature = "dev_compiler")]
     #[test]
-    fn step_trait()
+    fn dev_compiler() {}
+
+    #[cfg(rust_lang_feature = "diagnostic_namespace")]
+    #[test]
+    fn diagnostic_namespace()
     {
-        use std::iter::Step;
-        fn f<T: Step>(x: T) -> Option<T>
+        // Prevent old Rust versions from erroring on the attribute-path syntax.
+        macro_rules! shield {
+            () => {
+                #[diagnostic::on_unimplemented(
+                    message = "`{Self}` is not a shape",
+                    label = "not a shape",
+                    note = "implement `Shape` for it"
+                )]
+                trait Shape
+                {
+                    fn sides(&self) -> u32;
+                }
+
+                struct Square;
+
+                impl Shape for Square
+                {
+                    fn sides(&self) -> u32 { 4 }
+                }
+
+                assert_eq!(4, Square.sides());
+            };
+        }
+        shield!();
+    }
+
+    #[cfg(rust_lib_feature = "div_duration")]
+    #[test]
+    fn div_duration()
+    {
+        use std::time::Duration;
+
+        let three = Duration::from_secs(3);
+        assert_eq!(2.0, three.div_duration_f64(Duration::from_millis(1500)));
+        assert_eq!(0.5, Duration::from_millis(1500).div_duration_f32(three));
+        // A zero denominator does not panic, but gives the floating-point result.
+        assert_eq!(f64::INFINITY, three.div_duration_f64(Duration::from_secs(0)));
+        assert!(Duration::from_secs(0).div_duration_f64(Duration::from_secs(0)).is_nan());
+    }
+
+    #[cfg(rust_lib_feature = "duration_millis_float")]
+    #[test]
+    fn duration_millis_float()
+    {
+        use std::time::Duration;
+
+        assert_eq!(1000.0, Duration::from_secs(1).as_millis_f64());
+        assert_eq!(1.5, Duration::from_micros(1500).as_millis_f64());
+        assert_eq!(0.25, Duration::from_micros(250).as_millis_f32());
+    }
+
+    #[cfg(rust_lib_feature = "entry_insert")]
+    #[test]
+    fn entry_insert()
+    {
+        use std::collections::HashMap;
+
+        let mut m = HashMap::new();
+        let mut o = m.entry("a").insert_entry(1);
+        assert_eq!((&"a", &1), (o.key(), o.get()));
+        *o.get_mut() += 1;
+        assert_eq!(Some(&2), m.get("a"));
+
+        let o = m.entry("a").insert_entry(3);
+        assert_eq!(("a", 3), o.remove_entry());
+        assert!(m.is_empty());
+    }
+
+    #[cfg(rust_lib_feature = "error_generic_member_access")]
+    #[test]
+    fn error_generic_member_access()
+    {
+        use std::error::{request_ref, Error, Request};
+        use std::fmt;
+
+        #[derive(Debug)]
+        struct Wrapper;
+
+        impl fmt::Display for Wrapper
         {
-            Step::forward_checked(x, 1)
+            fn fmt(
+                &self,
+                f: &mut fmt::Formatter,
+            ) -> fmt::Result
+            {
+                f.write_str("wrapper")
+            }
         }
-        assert_eq!(Some(2), f(1))
+
+        impl Error for Wrapper
+        {
+            fn provide<'a>(
+                &'a self,
+                request: &mut Request<'a>,
+            )
+            {
+                let _ = request.provide_ref::<str>("member");
+            }
+        }
+
+        let e: &Error = &Wrapper;
+        assert_eq!(Some("member"), request_ref::<str>(e));
+        assert_eq!(None, request_ref::<String>(e));
+        // Also available from `core`.
+        assert_eq!(Some("member"), core::error::request_ref::<str>(e));
     }
 
-    // Similar to above, this exercises using a `cfg` option that is currently unsupported by the
-    // `cfg_rust_features` crate but that possibly could be supported in the future.
-    #[cfg(rust_lib_feature = "test")]
-    #[bench]
-    fn test(_bencher: &mut test::Bencher) {}
+    #[cfg(rust_lib_feature = "error_in_core")]
+    #[test]
+    fn error_in_core()
+    {
+        let e: &core::error::Error = &std::fmt::Error;
+        assert!(e.is::<std::fmt::Error>());
+    }
 
-    #[cfg(rust_comp_feature = "unstable_features")]
+    #[cfg(rust_lib_feature = "error_reporter")]
     #[test]
-    fn unstable_features()
+    fn error_reporter()
     {
-        #![allow(dead_code)]
+        use std::error::{Error, Report};
+        use std::fmt;
+
+        #[derive(Debug)]
+        struct Inner;
+
+        impl fmt::Display for Inner
+        {
+            fn fmt(
+                &self,
+                f: &mut fmt::Formatter,
+            ) -> fmt::Result
+            {
+                f.write_str("inner")
+            }
+        }
+
+        impl Error for Inner {}
+
+        #[derive(Debug)]
+        struct Outer(Inner);
+
+        impl fmt::Display for Outer
+        {
+            fn fmt(
+                &self,
+                f: &mut fmt::Formatter,
+            ) -> fmt::Result
+            {
+                f.write_str("outer")
+            }
+        }
+
+        impl Error for Outer
+        {
+            fn source(&self) -> Option<&(Error + 'static)>
+            {
+                Some(&self.0)
+            }
+        }
+
+        assert_eq!("outer: inner", Report::new(Outer(Inner)).to_string());
+        let pretty = Report::new(Outer(Inner)).pretty(true).to_string();
+        assert!(pretty.starts_with("outer\n"));
+        assert!(pretty.contains("Caused by:"));
+        assert!(pretty.contains("inner"));
     }
 
-    #[cfg(rust_lib_feature = "unwrap_infallible")]
+    #[cfg(rust_lib_feature = "exclusive_wrapper")]
     #[test]
-    fn unwrap_infallible()
+    fn exclusive_wrapper()
     {
-        assert_eq!(1, Ok::<_, never_type_hack::Never>(1).into_ok());
+        use std::cell::Cell;
+        use std::sync::SyncView;
+
+        fn assert_sync<T: Sync>() {}
+
+        assert_sync::<SyncView<Cell<u8>>>();
+        let mut exclusive = SyncView::new(Cell::new(1u8));
+        exclusive.as_mut().set(2);
+        assert_eq!(2, exclusive.as_mut().get());
+    }
+
+    #[cfg(rust_lib_feature = "exit_status_error")]
+    #[test]
+    fn exit_status_error()
+    {
+        use std::env;
+        use std::process::Command;
+
+        // Run this test executable itself, which is a command that is known to exist.
+        let this = env::current_exe().unwrap();
+        let run = |arg: &str| Command::new(&this).arg(arg).output().unwrap();
+
+        assert_eq!(Ok(()), run("--list").status.exit_ok());
+
+        let error = run("--bogus-option").status.exit_ok().unwrap_err();
+        assert!(!error.into_status().success());
+    }
+
+    #[cfg(rust_lib_feature = "extract_if")]
+    #[test]
+    fn extract_if()
+    {
+        let mut v = vec![1, 2, 3, 4, 5, 6];
+        let evens: Vec<_> = v.extract_if(.., |x| *x % 2 == 0).collect();
+        assert_eq!(vec![2, 4, 6], evens);
+        assert_eq!(vec![1, 3, 5], v);
+
+        let big: Vec<_> = v.extract_if(1 .., |x| *x > 1).collect();
+        assert_eq!(vec![3, 5], big);
+        assert_eq!(vec![1], v);
+    }
+
+    #[cfg(rust_lib_feature = "generic_nonzero")]
+    #[test]
+    fn generic_nonzero()
+    {
+        use std::num::{NonZero, NonZeroU32};
+
+        let n: NonZeroU32 = NonZero::<u32>::new(1).unwrap();
+        assert_eq!(1, n.get());
+        assert!(NonZero::<u32>::new(0).is_none());
+    }
+
+    #[cfg(rust_lib_feature = "hash_raw_entry")]
+    #[test]
+    fn hash_raw_entry()
+    {
+        use std::collections::HashMap;
+
+        let mut interned = HashMap::new();
+        for _ in 0 .. 2 {
+            let (_, count) =
+                interned.raw_entry_mut().from_key("a").or_insert(String::from("a"), 0);
+            *count += 1;
+        }
+        assert_eq!(1, interned.len());
+        assert_eq!(Some(&2), interned.get("a"));
+    }
+
+    #[cfg(rust_lib_feature = "hash_set_entry")]
+    #[test]
+    fn hash_set_entry()
+    {
+        use std::collections::HashSet;
+
+        let mut interner = HashSet::<String>::new();
+        let first = interner.get_or_insert_with("a", |s| s.to_string()) as *const String;
+        let second = interner.get_or_insert_with("a", |_| unreachable!()) as *const String;
+        assert_eq!(first, second);
+        assert_eq!(1, interner.len());
+    }
+
+    #[cfg(rust_lang_feature = "impl_trait_in_assoc_type")]
+    #[test]
+    fn impl_trait_in_assoc_type()
+    {
+        // Prevent old Rust versions from erroring on the `impl Trait` syntax.
+        macro_rules! shield {
+            () => {
+                struct Evens(u32);
+
+                impl IntoIterator for Evens
+                {
+                    type IntoIter = impl Iterator<Item = u32>;
+                    type Item = u32;
+
+                    fn into_iter(self) -> Self::IntoIter { (0 .. self.0).map(|x| x * 2) }
+                }
+
+                let evens: Vec<_> = Evens(3).into_iter().collect();
+                assert_eq!(vec![0, 2, 4], evens);
+            };
+        }
+        shield!();
+    }
+
+    #[cfg(rust_lang_feature = "inline_const_pat")]
+    #[test]
+    fn inline_const_pat()
+    {
+        // Prevent old Rust versions from erroring on the `const` block syntax.
+        macro_rules! shield {
+            () => {
+                const N: u8 = 2;
+                let name = |x: u8| match x {
+                    const { N } => "two",
+                    const { N * 2 } => "four",
+                    _ => "other",
+                };
+                assert_eq!(("two", "four", "other"), (name(2), name(4), name(3)));
+            };
+        }
+        shield!();
+    }
+
+    #[cfg(rust_lib_feature = "inner_deref")]
+    #[test]
+    fn inner_deref()
+    {
+        assert_eq!(Ok(&1), Ok::<_, ()>(Box::new(1)).as_deref());
+    }
+
+    #[cfg(all(unix, rust_lib_feature = "io_safety"))]
+    #[test]
+    fn io_safety()
+    {
+        use std::fs::File;
+        use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, OwnedFd};
+
+        let file = File::open("/dev/null").unwrap();
+        let raw = file.as_raw_fd();
+        let owned = OwnedFd::from(file);
+        let borrowed: BorrowedFd = owned.as_fd();
+        assert_eq!(raw, borrowed.as_raw_fd());
+        assert_eq!(raw, File::from(owned).as_raw_fd());
+    }
+
+    #[cfg(rust_lib_feature = "io_error_more")]
+    #[test]
+    fn io_error_more()
+    {
+        use std::io::{Error, ErrorKind};
+
+        let error = Error::new(ErrorKind::NotADirectory, "not a dir");
+        let described = match error.kind() {
+            ErrorKind::NotADirectory => "not a directory",
+            ErrorKind::IsADirectory => "is a directory",
+            ErrorKind::StorageFull => "storage full",
+            _ => "other",
+        };
+        assert_eq!("not a directory", described);
+    }
+
+    #[cfg(rust_lib_feature = "io_error_other")]
+    #[test]
+    fn io_error_other()
+    {
+        use std::io::{Error, ErrorKind};
+
+        let error = Error::other("shim no more");
+        assert_eq!(ErrorKind::Other, error.kind());
+        assert_eq!("shim no more", error.get_ref().unwrap().to_string());
+    }
+
+    #[cfg(rust_lib_feature = "is_none_or")]
+    #[test]
+    fn is_none_or()
+    {
+        assert!(None::<i32>.is_none_or(|x| x > 0));
+        assert!(Some(1).is_none_or(|x| x > 0));
+        assert!(!Some(-1).is_none_or(|x| x > 0));
+    }
+
+    #[cfg(rust_lib_feature = "iter_advance_by")]
+    #[test]
+    fn iter_advance_by()
+    {
+        let mut frames = 0 .. 5;
+        assert!(frames.advance_by(2).is_ok());
+        assert_eq!(Some(2), frames.next());
+        assert!(frames.advance_by(0).is_ok());
+        // The error's type has changed, and so only whether it is an error is checked.
+        assert!(frames.advance_by(3).is_err());
+        assert_eq!(None, frames.next());
+    }
+
+    #[cfg(rust_lib_feature = "iter_array_chunks")]
+    #[test]
+    fn iter_array_chunks()
+    {
+        // Prevent old Rust versions from erroring on the const-generic argument syntax.
+        macro_rules! shield {
+            () => {
+                let mut chunks = (0 .. 5).array_chunks::<2>();
+                assert_eq!(Some([0, 1]), chunks.next());
+                assert_eq!(Some([2, 3]), chunks.next());
+                assert_eq!(None, chunks.next());
+                let remainder: Vec<_> = chunks.into_remainder().collect();
+                assert_eq!(vec![4], remainder);
+                // The remainder is not counted as a chunk.
+                assert_eq!(2, [1, 2, 3, 4, 5].iter().array_chunks::<2>().count());
+            };
+        }
+        shield!();
+    }
+
+    #[cfg(rust_lib_feature = "iter_collect_into")]
+    #[test]
+    fn iter_collect_into()
+    {
+        let mut v = Vec::with_capacity(8);
+        v.push(0);
+        let ptr = v.as_ptr();
+        let _ = (1 .. 4).collect_into(&mut v);
+        assert_eq!(vec![0, 1, 2, 3], v);
+        let _ = (4 .. 6).collect_into(&mut v);
+        assert_eq!(vec![0, 1, 2, 3, 4, 5], v);
+        assert_eq!((ptr, 8), (v.as_ptr(), v.capacity()));
+    }
+
+    #[cfg(rust_lib_feature = "iter_intersperse")]
+    #[test]
+    fn iter_intersperse()
+    {
+        // Fully qualified, so that no other trait's method of the same name could be used.
+        let words = vec!["a", "b", "c"].into_iter();
+        let joined: String = Iterator::intersperse(words, ", ").collect();
+        assert_eq!("a, b, c", joined);
+
+        let mut n = 0;
+        let ones = vec![1, 1].into_iter();
+        let counted: Vec<_> = Iterator::intersperse_with(ones, || {
+            n += 1;
+            0
+        })
+        .collect();
+        assert_eq!((vec![1, 0, 1], 1), (counted, n));
+    }
+
+    #[cfg(rust_lib_feature = "iter_map_windows")]
+    #[test]
+    fn iter_map_windows()
+    {
+        let sums: Vec<_> = (1 .. 5).map_windows(|w: &[i32; 2]| w[0] + w[1]).collect();
+        assert_eq!(vec![3, 5, 7], sums);
+
+        let too_short: Vec<_> = (1 .. 3).map_windows(|w: &[i32; 3]| w[0]).collect();
+        assert!(too_short.is_empty());
+    }
+
+    #[cfg(rust_lib_feature = "iter_next_chunk")]
+    #[test]
+    fn iter_next_chunk()
+    {
+        // Prevent old Rust versions from erroring on the const-generic argument syntax.
+        macro_rules! shield {
+            () => {
+                let mut header = 0u8 .. 3;
+                assert_eq!(Some([0, 1]), header.next_chunk::<2>().ok());
+                match header.next_chunk::<2>() {
+                    Ok(_) => panic!("fewer than 2 should remain"),
+                    Err(partial) => assert_eq!(vec![2], partial.collect::<Vec<_>>()),
+                }
+            };
+        }
+        shield!();
+    }
+
+    #[cfg(rust_lib_feature = "iter_partition_in_place")]
+    #[test]
+    fn iter_partition_in_place()
+    {
+        let mut a = [1, 2, 3, 4, 5, 6, 7];
+        let evens = a.iter_mut().partition_in_place(|&x| x % 2 == 0);
+        assert_eq!(3, evens);
+        assert!(a[.. evens].iter().all(|&x| x % 2 == 0));
+        assert!(a[evens ..].iter().all(|&x| x % 2 != 0));
+    }
+
+    #[cfg(rust_lib_feature = "iter_zip")]
+    #[test]
+    fn iter_zip()
+    {
+        assert_eq!(vec![(1, 2)], std::iter::zip([1], [2]).collect::<Vec<_>>());
+    }
+
+    #[cfg(rust_lib_feature = "iterator_try_collect")]
+    #[test]
+    fn iterator_try_collect()
+    {
+        let all: Result<Vec<i32>, &str> = vec![Ok(1), Ok(2)].into_iter().try_collect();
+        assert_eq!(Ok(vec![1, 2]), all);
+
+        let mut results = vec![Ok(1), Err("bad"), Ok(3)].into_iter();
+        let failed: Result<Vec<i32>, &str> = results.by_ref().try_collect();
+        assert_eq!(Err("bad"), failed);
+        // Short-circuited at the error.
+        assert_eq!(Some(Ok(3)), results.next());
+
+        let generic = vec![Ok::<_, ()>(1)].into_iter().try_collect::<Vec<_>>();
+        assert_eq!(Ok(vec![1]), generic);
+
+        let some: Option<Vec<u8>> = vec![Some(1), None].into_iter().try_collect();
+        assert_eq!(None, some);
+    }
+
+    #[cfg(rust_lib_feature = "linked_list_cursors")]
+    #[test]
+    fn linked_list_cursors()
+    {
+        use std::collections::LinkedList;
+
+        let mut list: LinkedList<_> = vec![1, 3, 5].into_iter().collect();
+        {
+            let mut cursor = list.cursor_front_mut();
+            assert_eq!(Some(&mut 1), cursor.current());
+            cursor.insert_before(0);
+            cursor.move_next();
+            cursor.insert_after(4);
+            cursor.insert_before(2);
+            assert_eq!(Some(&mut 3), cursor.current());
+            cursor.move_next();
+            cursor.move_next();
+            assert_eq!(Some(5), cursor.remove_current());
+        }
+        let all: Vec<_> = list.into_iter().collect();
+        assert_eq!(vec![0, 1, 2, 3, 4], all);
+    }
+
+    #[cfg(rust_lib_feature = "map_first_last")]
+    #[test]
+    fn map_first_last()
+    {
+        use std::collections::{BTreeMap, BTreeSet};
+
+        let mut deadlines = BTreeMap::new();
+        deadlines.insert(20, 'b');
+        deadlines.insert(10, 'a');
+        deadlines.insert(30, 'c');
+        assert_eq!(Some((&10, &'a')), deadlines.first_key_value());
+        assert_eq!(Some((&30, &'c')), deadlines.last_key_value());
+        assert_eq!(Some((10, 'a')), deadlines.pop_first());
+        assert_eq!(Some((30, 'c')), deadlines.pop_last());
+        assert_eq!(Some((20, 'b')), deadlines.pop_first());
+        assert_eq!(None, deadlines.first_key_value());
+        assert_eq!(None, deadlines.pop_last());
+
+        let mut set = BTreeSet::new();
+        set.insert(2);
+        set.insert(1);
+        assert_eq!((Some(&1), Some(&2)), (set.first(), set.last()));
+        assert_eq!(Some(1), set.pop_first());
+    }
+
+    #[cfg(rust_lib_feature = "maybe_uninit_slice")]
+    #[test]
+    fn maybe_uninit_slice()
+    {
+        use std::mem::MaybeUninit;
+
+        let mut buf = [MaybeUninit::<u8>::uninit(); 3];
+        for (i, elem) in buf.iter_mut().enumerate() {
+            unsafe { elem.as_mut_ptr().write(i as u8) };
+        }
+        assert_eq!(&[0, 1, 2], unsafe { buf.assume_init_ref() });
+    }
+
+    #[cfg(rust_lib_feature = "maybe_uninit_uninit_array")]
+    #[test]
+    fn maybe_uninit_uninit_array()
+    {
+        // Prevent old Rust versions from erroring on the const-generic argument syntax.
+        macro_rules! shield {
+            () => {
+                use std::mem::MaybeUninit;
+
+                let mut array = MaybeUninit::<u8>::uninit_array::<4>();
+                for (i, elem) in array.iter_mut().enumerate() {
+                    let _ = elem.write(i as u8);
+                }
+                let values: Vec<u8> =
+                    array.iter().map(|elem| unsafe { elem.assume_init() }).collect();
+                assert_eq!(vec![0, 1, 2, 3], values);
+            };
+        }
+        shield!();
+    }
+
+    #[cfg(rust_lib_feature = "mpmc_channel")]
+    #[test]
+    fn mpmc_channel()
+    {
+        use std::sync::mpmc::channel;
+        use std::thread;
+
+        let (tx, rx) = channel();
+        let rx2 = rx.clone();
+        let sender = thread::spawn(move || {
+            tx.send(1).unwrap();
+            tx.send(2).unwrap();
+        });
+        sender.join().unwrap();
+        // Multiple consumers.
+        assert_eq!(1, rx.recv().unwrap());
+        assert_eq!(2, rx2.recv().unwrap());
+        // Every clone sees the disconnection.
+        assert!(rx.recv().is_err());
+        assert!(rx2.recv().is_err());
+    }
+
+    #[cfg(rust_lib_feature = "mutex_unpoison")]
+    #[test]
+    fn mutex_unpoison()
+    {
+        use std::panic;
+        use std::sync::Mutex;
+
+        let mutex = Mutex::new(1);
+        {
+            let _guard = panic_hook_lock::lock();
+            let caught = panic::catch_unwind(|| {
+                let _locked = mutex.lock().unwrap();
+                panic!("poisoning");
+            });
+            assert!(caught.is_err());
+        }
+        assert!(mutex.is_poisoned());
+        mutex.clear_poison();
+        assert!(!mutex.is_poisoned());
+        assert_eq!(1, *mutex.lock().unwrap());
+    }
+
+    #[cfg(rust_lang_feature = "never_type")]
+    #[test]
+    fn never_type()
+    {
+        // Prevent old Rust versions from erroring on the `!` syntax.
+        macro_rules! shield {
+            () => {
+                let _: [!; 0] = [];
+            };
+        }
+        shield!();
+    }
+
+    #[cfg(rust_lib_feature = "new_uninit")]
+    #[test]
+    fn new_uninit()
+    {
+        use std::rc::Rc;
+
+        let mut boxed = Box::<u32>::new_uninit();
+        let _ = boxed.write(7);
+        assert_eq!(7, *unsafe { boxed.assume_init() });
+
+        let mut slice = Rc::<[u8]>::new_uninit_slice(3);
+        for (i, elem) in Rc::get_mut(&mut slice).unwrap().iter_mut().enumerate() {
+            let _ = elem.write(i as u8);
+        }
+        assert_eq!(&[0, 1, 2], &*unsafe { slice.assume_init() });
+    }
+
+    #[cfg(rust_lib_feature = "option_get_or_insert_default")]
+    #[test]
+    fn option_get_or_insert_default()
+    {
+        let mut o = None::<Vec<u8>>;
+        {
+            let v = o.get_or_insert_default();
+            assert!(v.is_empty());
+            v.push(1);
+        }
+        assert_eq!(Some(vec![1]), o);
+        // Not inserted again, since already `Some`.
+        o.get_or_insert_default().push(2);
+        assert_eq!(Some(vec![1, 2]), o);
+    }
+
+    #[cfg(rust_lib_feature = "option_take_if")]
+    #[test]
+    fn option_take_if()
+    {
+        let mut o = Some(1);
+        assert_eq!(None, o.take_if(|v: &mut i32| *v == 2));
+        assert_eq!(Some(1), o);
+        assert_eq!(Some(1), o.take_if(|v: &mut i32| *v == 1));
+        assert_eq!(None, o);
+        assert_eq!(None, o.take_if(|_: &mut i32| true));
+        assert_eq!(None, o);
+    }
+
+    #[cfg(rust_comp_feature = "panic_abort")]
+    #[test]
+    fn panic_abort() {}
+
+    #[cfg(rust_lib_feature = "panic_info_message")]
+    #[test]
+    fn panic_info_message()
+    {
+        use std::fs::{self, File};
+        use std::io::Write;
+        use std::process::{self, Command};
+        use std::{env, str};
+
+        // The `PanicInfo` given to a `#[panic_handler]`, which a test cannot install, has the
+        // message.  (A hook is given a `PanicHookInfo` instead, which does not.)
+        fn handler_message(info: &core::panic::PanicInfo) -> String
+        {
+            info.message().to_string()
+        }
+        let _ = handler_message;
+
+        // And so a `no_std` program with such a handler, which prints the `Display` of the
+        // message, is built and run.  Linking such is only done for Linux here, for simplicity.
+        const PROGRAM: &'static str = r#"
+            #![no_std]
+            #![no_main]
+
+            use core::fmt::Write;
+            use core::panic::PanicInfo;
+
+            #[link(name = "c")]
+            extern "C" {
+                fn write(fd: i32, buf: *const u8, count: usize) -> isize;
+                fn exit(status: i32) -> !;
+            }
+
+            struct Stdout;
+
+            impl Write for Stdout {
+                fn write_str(&mut self, s: &str) -> core::fmt::Result {
+                    unsafe { write(1, s.as_ptr(), s.len()) };
+                    Ok(())
+                }
+            }
+
+            #[panic_handler]
+            fn panic(info: &PanicInfo) -> ! {
+                let _ = write!(Stdout, "{}", info.message());
+                unsafe { exit(0) }
+            }
+
+            #[no_mangle]
+            pub extern "C" fn rust_eh_personality() {}
+
+            #[no_mangle]
+            pub extern "C" fn main() -> i32 {
+                let n = 42;
+                panic!("structured message {}", n)
+            }
+        "#;
+
+        if cfg!(target_os = "linux") {
+            let dir = env::temp_dir().join(format!("tfac-panic_info_message-{}", process::id()));
+            fs::create_dir_all(&dir).unwrap();
+            let source = dir.join("panic_info_message.rs");
+            let program = dir.join("panic_info_message");
+            File::create(&source).unwrap().write_all(PROGRAM.as_bytes()).unwrap();
+
+            let rustc = env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+            let built = Command::new(rustc)
+                .args(&["-C", "panic=abort", "-o"])
+                .arg(&program)
+                .arg(&source)
+                .status()
+                .unwrap();
+            assert!(built.success());
+
+            let output = Command::new(&program).output().unwrap();
+            assert!(output.status.success());
+            assert_eq!(Ok("structured message 42"), str::from_utf8(&output.stdout));
+            fs::remove_dir_all(&dir).unwrap();
+        }
+    }
+
+    #[cfg(rust_lib_feature = "panic_update_hook")]
+    #[test]
+    fn panic_update_hook()
+    {
+        use std::panic;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let _guard = panic_hook_lock::lock();
+        let inner = Arc::new(AtomicUsize::new(0));
+        let outer = Arc::new(AtomicUsize::new(0));
+        let previous = panic::take_hook();
+
+        let inner_count = inner.clone();
+        panic::set_hook(Box::new(move |_| {
+            let _ = inner_count.fetch_add(1, Ordering::SeqCst);
+        }));
+        let outer_count = outer.clone();
+        panic::update_hook(move |prev, info| {
+            let _ = outer_count.fetch_add(1, Ordering::SeqCst);
+            prev(info);
+        });
+        let caught = panic::catch_unwind(|| panic!("layered"));
+        panic::set_hook(previous);
+
+        assert!(caught.is_err());
+        assert_eq!(1, inner.load(Ordering::SeqCst));
+        assert_eq!(1, outer.load(Ordering::SeqCst));
+    }
+
+    #[cfg(rust_lib_feature = "pattern")]
+    #[test]
+    fn pattern()
+    {
+        use std::str::pattern::{Pattern, SearchStep, Searcher};
+
+        fn count<P: Pattern>(haystack: &str, pat: P) -> usize
+        {
+            haystack.matches(pat).count()
+        }
+
+        assert_eq!(2, count("a,b;c", |c: char| c == ',' || c == ';'));
+        assert_eq!(1, count("a,b;c", ";"));
+        let mut searcher = 'b'.into_searcher("ab");
+        assert_eq!(SearchStep::Reject(0, 1), searcher.next());
+        assert_eq!(SearchStep::Match(1, 2), searcher.next());
+    }
+
+    #[cfg(rust_lib_feature = "portable_simd")]
+    #[test]
+    fn portable_simd()
+    {
+        use std::simd::num::SimdInt;
+        use std::simd::Simd;
+
+        let v = Simd::from_array([1, 2, 3, 4]);
+        assert_eq!(10, v.reduce_sum());
+        let w: Simd<i32, _> = v + Simd::splat(1);
+        assert_eq!([2, 3, 4, 5], w.to_array());
+    }
+
+    #[cfg(rust_lib_feature = "process_exitcode")]
+    #[test]
+    fn process_exitcode()
+    {
+        use std::process::ExitCode;
+
+        // (`ExitCode` only became `PartialEq` after it was stabilized.)
+        let same = |a: ExitCode, b: ExitCode| format!("{:?}", a) == format!("{:?}", b);
+        assert!(same(ExitCode::SUCCESS, ExitCode::from(0)));
+        assert!(same(ExitCode::FAILURE, ExitCode::from(1)));
+        assert!(!same(ExitCode::SUCCESS, ExitCode::from(2)));
+    }
+
+    #[cfg(rust_lang_feature = "question_mark")]
+    #[test]
+    fn question_mark()
+    {
+        // Prevent old Rust versions from erroring on the `?` syntax.
+        macro_rules! shield {
+            () => {
+                Err(())?
+            };
+        }
+        fn f() -> Result<(), ()>
+        {
+            shield!()
+        }
+        assert_eq!(Err(()), f());
+    }
+
+    #[cfg(rust_lib_feature = "raw_ref_macros")]
+    #[test]
+    fn raw_ref_macros()
+    {
+        // Prevent old Rust versions from erroring on the macro-path syntax.
+        macro_rules! shield {
+            () => {
+                let x = 1u8;
+                let p: *const u8 = std::ptr::addr_of!(x);
+                assert_eq!(1, unsafe { *p });
+
+                let mut y = 2u8;
+                let p: *mut u8 = std::ptr::addr_of_mut!(y);
+                unsafe { *p = 3 };
+                assert_eq!(3, y);
+            };
+        }
+        shield!();
+    }
+
+    #[cfg(rust_lib_feature = "read_buf")]
+    #[test]
+    fn read_buf()
+    {
+        use std::io::{BorrowedBuf, Read};
+        use std::mem::MaybeUninit;
+
+        let mut storage = [0u8; 8];
+        let mut buf = BorrowedBuf::from(&mut storage[..]);
+        assert_eq!((8, 0), (buf.capacity(), buf.len()));
+
+        let mut src: &[u8] = &[1, 2, 3];
+        src.read_buf(buf.unfilled()).unwrap();
+        assert_eq!(&[1, 2, 3], buf.filled());
+
+        // Without needing to initialize the storage first.
+        let mut uninit = [MaybeUninit::<u8>::uninit(); 64];
+        let mut buf = BorrowedBuf::from(&mut uninit[..]);
+        let mut src: &[u8] = b"no zeroing";
+        src.read_buf(buf.unfilled()).unwrap();
+        assert_eq!(b"no zeroing", buf.filled());
+    }
+
+    #[cfg(rust_lib_feature = "result_flattening")]
+    #[test]
+    fn result_flattening()
+    {
+        assert_eq!(Ok(1), Ok::<Result<i32, &str>, &str>(Ok(1)).flatten());
+        assert_eq!(Err("inner"), Ok::<Result<i32, &str>, &str>(Err("inner")).flatten());
+        assert_eq!(Err("outer"), Err::<Result<i32, &str>, &str>("outer").flatten());
+    }
+
+    #[cfg(rust_comp_feature = "rust1")]
+    #[test]
+    fn rust1_comp() {}
+
+    #[cfg(rust_lang_feature = "rust1")]
+    #[test]
+    fn rust1_lang() {}
+
+    #[cfg(rust_lib_feature = "rust1")]
+    #[test]
+    fn rust1_lib() {}
+
+    #[cfg(rust_lib_feature = "scoped_threads")]
+    #[test]
+    fn scoped_threads()
+    {
+        use std::thread;
+
+        let numbers = vec![1, 2, 3, 4, 5, 6];
+        let (left, right) = numbers.split_at(3);
+        let (a, b) = thread::scope(|s| {
+            let a = s.spawn(|| left.iter().sum::<i32>());
+            let b = s.spawn(|| right.iter().sum::<i32>());
+            (a.join().unwrap(), b.join().unwrap())
+        });
+        assert_eq!((6, 15), (a, b));
+        assert_eq!(21, a + b);
+    }
+
+    #[cfg(rust_lib_feature = "slice_as_chunks")]
+    #[test]
+    fn slice_as_chunks()
+    {
+        // Prevent old Rust versions from erroring on the const-generic argument syntax.
+        macro_rules! shield {
+            () => {
+                let buf = [1u8, 2, 3, 4, 5];
+                let (chunks, remainder) = buf.as_chunks::<2>();
+                assert_eq!(&[[1, 2], [3, 4]], chunks);
+                assert_eq!(&[5], remainder);
+                let (remainder, chunks) = buf.as_rchunks::<2>();
+                assert_eq!(&[1], remainder);
+                assert_eq!(&[[2, 3], [4, 5]], chunks);
+            };
+        }
+        shield!();
+    }
+
+    #[cfg(rust_lib_feature = "slice_fill")]
+    #[test]
+    fn slice_fill()
+    {
+        let mut buf = [0u8; 4];
+        buf[1 ..].fill(7);
+        assert_eq!([0, 7, 7, 7], buf);
+    }
+
+    #[cfg(rust_lib_feature = "slice_fill_with")]
+    #[test]
+    fn slice_fill_with()
+    {
+        let mut next = 0;
+        let mut buf = [0u8; 4];
+        buf.fill_with(|| {
+            next += 1;
+            next
+        });
+        assert_eq!([1, 2, 3, 4], buf);
+    }
+
+    #[cfg(rust_lib_feature = "slice_first_last_chunk")]
+    #[test]
+    fn slice_first_last_chunk()
+    {
+        // Prevent old Rust versions from erroring on the const-generic argument syntax.
+        macro_rules! shield {
+            () => {
+                let buf = [1u8, 2, 3, 4, 5, 6, 7, 8];
+                let (header, rest) = buf.split_first_chunk::<4>().unwrap();
+                assert_eq!(&[1, 2, 3, 4], header);
+                assert_eq!(&[5, 6, 7, 8], rest);
+                assert_eq!(None, buf[..3].split_first_chunk::<4>());
+            };
+        }
+        shield!();
+    }
+
+    #[cfg(rust_lib_feature = "slice_take")]
+    #[test]
+    fn slice_take()
+    {
+        let mut cursor: &[u8] = &[1, 2, 3];
+        assert_eq!(Some(&1), cursor.split_off_first());
+        assert_eq!(&[2, 3], cursor);
+        assert_eq!(Some(&2), cursor.split_off_first());
+        assert_eq!(&[3], cursor);
+        assert_eq!(Some(&3), cursor.split_off_first());
+        assert!(cursor.is_empty());
+        assert_eq!(None, cursor.split_off_first());
+
+        let mut cursor: &[u8] = &[1, 2, 3, 4, 5];
+        assert_eq!(Some(&[1, 2][..]), cursor.split_off(.. 2));
+        assert_eq!(Some(&5), cursor.split_off_last());
+        assert_eq!(Some(&[4][..]), cursor.split_off(1 ..));
+        assert_eq!(&[3], cursor);
+        assert_eq!(None, cursor.split_off(.. 2));
+    }
+
+    #[cfg(rust_lib_feature = "step_trait")]
+    #[test]
+    fn step_trait()
+    {
+        use std::iter::Step;
+        fn f<T: Step>(x: T) -> Option<T>
+        {
+            Step::forward_checked(x, 1)
+        }
+        assert_eq!(Some(2), f(1))
+    }
+
+    #[cfg(rust_lib_feature = "sync_exclusive")]
+    #[test]
+    fn sync_exclusive()
+    {
+        use std::cell::Cell;
+        use std::sync::SyncView;
+
+        fn assert_sync<T: Sync>(_: &T) {}
+
+        let mut exclusive = SyncView::new(Cell::new(1));
+        exclusive.as_mut().set(2);
+        assert_sync(&exclusive);
+        assert_eq!(2, exclusive.into_inner().get());
+    }
+
+    #[cfg(rust_comp_feature = "target_abi")]
+    #[test]
+    fn target_abi() {}
+
+    #[cfg(rust_comp_feature = "target_has_atomic")]
+    #[test]
+    fn target_has_atomic()
+    {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let a = AtomicUsize::new(1);
+        assert_eq!(1, a.fetch_add(1, Ordering::SeqCst));
+        assert_eq!(2, a.load(Ordering::SeqCst));
+    }
+
+    // Similar to above, this exercises using a `cfg` option that is currently unsupported by the
+    // `cfg_rust_features` crate but that possibly could be supported in the future.
+    #[cfg(rust_lib_feature = "test")]
+    #[bench]
+    fn test(_bencher: &mut test::Bencher) {}
+
+    #[cfg(rust_lib_feature = "thread_sleep_until")]
+    #[test]
+    fn thread_sleep_until()
+    {
+        use std::thread::sleep_until;
+        use std::time::{Duration, Instant};
+
+        let start = Instant::now();
+        sleep_until(start);
+        sleep_until(start - Duration::from_millis(1));
+        assert!(start.elapsed() < Duration::from_secs(1));
+
+        let deadline = Instant::now() + Duration::from_millis(20);
+        sleep_until(deadline);
+        assert!(Instant::now() >= deadline);
+    }
+
+    #[cfg(rust_lib_feature = "try_find")]
+    #[test]
+    fn try_find()
+    {
+        let is_even = |&x: &i32| if x < 0 { Err("negative") } else { Ok(x % 2 == 0) };
+
+        assert_eq!(Ok(Some(2)), vec![1, 2, 3].into_iter().try_find(&is_even));
+        assert_eq!(Ok(None), vec![1, 3].into_iter().try_find(&is_even));
+        let mut checked = vec![1, -1, 2].into_iter();
+        assert_eq!(Err("negative"), checked.try_find(&is_even));
+        assert_eq!(Some(2), checked.next());
+    }
+
+    #[cfg(rust_lib_feature = "try_reserve")]
+    #[test]
+    fn try_reserve()
+    {
+        let mut v = Vec::<u8>::new();
+        assert!(v.try_reserve(16).is_ok());
+        assert!(v.capacity() >= 16);
+        assert!(v.try_reserve(std::usize::MAX).is_err());
+
+        let mut s = String::new();
+        assert!(s.try_reserve_exact(8).is_ok());
+        assert!(s.try_reserve(std::usize::MAX).is_err());
+    }
+
+    #[cfg(rust_lib_feature = "try_trait_v2")]
+    #[test]
+    fn try_trait_v2()
+    {
+        use std::convert::Infallible;
+        use std::ops::{ControlFlow, FromResidual, Try};
+
+        #[derive(PartialEq, Debug)]
+        struct Outcome(Option<i32>);
+
+        impl Try for Outcome
+        {
+            type Output = i32;
+            type Residual = Option<Infallible>;
+
+            fn from_output(output: i32) -> Self
+            {
+                Outcome(Some(output))
+            }
+
+            fn branch(self) -> ControlFlow<Option<Infallible>, i32>
+            {
+                match self.0 {
+                    Some(output) => ControlFlow::Continue(output),
+                    None => ControlFlow::Break(None),
+                }
+            }
+        }
+
+        impl FromResidual<Option<Infallible>> for Outcome
+        {
+            fn from_residual(_: Option<Infallible>) -> Self
+            {
+                Outcome(None)
+            }
+        }
+
+        // Prevent old Rust versions from erroring on the `?` syntax.
+        macro_rules! shield {
+            ($e:expr) => {
+                $e?
+            };
+        }
+        fn increment(outcome: Outcome) -> Outcome
+        {
+            Outcome(Some(shield!(outcome) + 1))
+        }
+        assert_eq!(Outcome(Some(2)), increment(Outcome(Some(1))));
+        assert_eq!(Outcome(None), increment(Outcome(None)));
+    }
+
+    #[cfg(rust_comp_feature = "unstable_features")]
+    #[test]
+    fn unstable_features()
+    {
+        #![allow(dead_code)]
+    }
+
+    #[cfg(rust_lib_feature = "unwrap_infallible")]
+    #[test]
+    fn unwrap_infallible()
+    {
+        assert_eq!(1, Ok::<_, never_type_hack::Never>(1).into_ok());
+    }
+
+    #[cfg(rust_lib_feature = "vec_into_raw_parts")]
+    #[test]
+    fn vec_into_raw_parts()
+    {
+        let mut v = Vec::with_capacity(8);
+        v.extend(vec![1u16, 2, 3]);
+        let (ptr, len, cap) = v.into_raw_parts();
+        assert_eq!((3, 8), (len, cap));
+        let v = unsafe { Vec::from_raw_parts(ptr, len, cap) };
+        assert_eq!(&[1, 2, 3], &*v);
+        assert_eq!(8, v.capacity());
+    }
+
+    #[cfg(rust_lib_feature = "vec_push_within_capacity")]
+    #[test]
+    fn vec_push_within_capacity()
+    {
+        let mut v = Vec::with_capacity(2);
+        let cap = v.capacity();
+        for i in 0 .. cap {
+            assert!(v.push_within_capacity(i).is_ok());
+        }
+        assert_eq!(Err(cap), v.push_within_capacity(cap).map(|_| ()));
+        assert_eq!(cap, v.len());
+        assert_eq!(cap, v.capacity());
+    }
+
+    #[cfg(rust_lib_feature = "vec_split_at_spare")]
+    #[test]
+    fn vec_split_at_spare()
+    {
+        let mut v = Vec::with_capacity(4);
+        v.push(1u8);
+        {
+            let (init, spare) = v.split_at_spare_mut();
+            assert_eq!(&[1], init);
+            assert!(spare.len() >= 3);
+            init[0] = 2;
+            spare[0] = std::mem::MaybeUninit::new(3);
+        }
+        unsafe { v.set_len(2) };
+        assert_eq!(vec![2, 3], v);
+    }
+
+    #[cfg(rust_comp_feature = "wasm")]
+    #[test]
+    fn wasm()
+    {
+        assert!(cfg!(target_arch = "wasm32") || cfg!(target_arch = "wasm64"));
+    }
+
+    #[cfg(all(windows, rust_lib_feature = "windows_io_safety"))]
+    #[test]
+    fn windows_io_safety()
+    {
+        use std::fs::File;
+        use std::os::windows::io::{AsHandle, AsRawHandle, BorrowedHandle, OwnedHandle};
+
+        let file = File::open("NUL").unwrap();
+        let raw = file.as_raw_handle();
+        let owned = OwnedHandle::from(file);
+        let borrowed: BorrowedHandle = owned.as_handle();
+        assert_eq!(raw, borrowed.as_raw_handle());
+        assert_eq!(raw, File::from(owned).as_raw_handle());
+    }
+
+    #[cfg(rust_lib_feature = "write_all_vectored")]
+    #[test]
+    fn write_all_vectored()
+    {
+        use std::io::{IoSlice, Write};
+
+        let mut out = Vec::new();
+        let mut slices = [IoSlice::new(b"log "), IoSlice::new(b"line")];
+        // The slices are given mutably because the call may modify them to track its progress,
+        // and so their contents afterward are unspecified and must not be relied on.
+        out.write_all_vectored(&mut slices).unwrap();
+        assert_eq!(b"log line", &out[..]);
     }
 
     // This exercises using a non-existent feature that both Rust and the `cfg_rust_features`
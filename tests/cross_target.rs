@@ -0,0 +1,40 @@
+// Note: This will print to stderr what look like errors but these are only from autocfg probing
+// for a target other than the host, which is intended.
+//
+// This is its own test program, because it changes the `TARGET` environment variable, which would
+// interfere with other tests that run concurrently in the same process.
+
+extern crate cfg_rust_features;
+extern crate create_temp_subdir;
+
+use std::env;
+
+use cfg_rust_features::{CfgRustFeatures, ProbeMethod};
+use create_temp_subdir::TempSubDir;
+
+
+/// Some features that are only in `std`, and that would otherwise be decided by the version
+/// table.  Both must be stable since versions at or below its cutoff (here 1.59 and 1.61), i.e.
+/// at least one minor version before the `stable` `rustc` that runs this test.
+const STD_ONLY: &'static [&'static str] = &["available_parallelism", "process_exitcode"];
+
+#[test]
+fn version_table_not_used_when_cross_compiling()
+{
+    let out_dir = TempSubDir::new("test-cross_target").unwrap();
+    env::set_var("OUT_DIR", &out_dir);
+    // A target other than the host, as given by Cargo when cross-compiling.  (It also lacks
+    // `std`, so the probes fail whether or not the target is installed.)
+    env::set_var("TARGET", "thumbv7em-none-eabihf");
+    env::set_var("HOST", "x86_64-unknown-linux-gnu");
+
+    for &prefer in &[false, true] {
+        let cfg_rust_features =
+            CfgRustFeatures::builder().prefer_version_table(prefer).build().unwrap();
+        let outcomes = cfg_rust_features.probe_multiple_detailed(STD_ONLY.to_vec()).unwrap();
+        for (name, outcome) in &outcomes {
+            assert!(outcome.method != ProbeMethod::VersionTable, "{}", name);
+            assert!(!outcome.enabled, "{}", name);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+// This is its own test program, because it changes the `RUSTC` environment variable, which would
+// interfere with other tests that run concurrently in the same process.
+
+extern crate cfg_rust_features;
+extern crate create_temp_subdir;
+
+use std::env;
+
+use cfg_rust_features::{CfgRustFeatures, OnProbeError};
+use create_temp_subdir::TempSubDir;
+
+
+#[test]
+fn unusable_rustc()
+{
+    let out_dir = TempSubDir::new("test-on_probe_error").unwrap();
+    env::set_var("OUT_DIR", &out_dir);
+    env::set_var("RUSTC", "/nonexistent/cfg_rust_features-test/rustc");
+
+    assert!(CfgRustFeatures::builder().build().is_err());
+    assert!(CfgRustFeatures::builder().on_probe_error(OnProbeError::Strict).build().is_err());
+
+    for &on_probe_error in &[OnProbeError::AssumeDisabled, OnProbeError::AssumeDisabledQuietly] {
+        let cfg_rust_features =
+            CfgRustFeatures::builder().on_probe_error(on_probe_error).build().unwrap();
+        let enabled_features =
+            cfg_rust_features.probe_multiple(vec!["rust1", "question_mark", "iter_zip"]).unwrap();
+        assert!(enabled_features["rust1"].is_some());
+        assert!(enabled_features["question_mark"].is_none());
+        assert!(enabled_features["iter_zip"].is_none());
+    }
+}
@@ -16,7 +16,8 @@ use std::error::Error;
 use std::hash::Hash;
 use std::iter::FromIterator;
 
-use cfg_rust_features::{emit_rerun_if_changed_file, CfgRustFeatures, FeatureCategory};
+use cfg_rust_features::{emit_rerun_if_changed_file, enabled_only, CfgRustFeatures,
+                        FeatureCategory};
 use create_temp_subdir::TempSubDir;
 
 type ResultDynErr<T> = Result<T, Box<Error>>;
@@ -31,18 +32,100 @@ fn pretend_build_script() -> ResultDynErr<EnabledFeatures>
     emit_rerun_if_changed_file(file!());
 
     Ok(try!(try!(CfgRustFeatures::new()).emit_multiple(vec![
+        "allocator_api",
+        "anonymous_pipe",
         "arbitrary_self_types",
+        "atomic_from_mut",
+        "available_parallelism",
+        "backtrace",
+        "binary_heap_drain_sorted",
+        "binary_heap_into_iter_sorted",
+        "black_box",
+        "btree_cursors",
         // "cfg_version",  // Omitted to exercise not giving a supported one.
+        "closure_captures",
+        "cmp_minmax",
+        "const_assert",
+        "const_cstr_methods",
+        "const_fn_float_arithmetic",
+        "const_let_else",
+        "const_panic",
+        "const_slice_first",
+        "core_net",
+        "dev_compiler",
+        "diagnostic_namespace",
+        "div_duration",
+        "duration_millis_float",
+        "entry_insert",
+        "error_generic_member_access",
+        "error_reporter",
+        "exclusive_wrapper",
+        "exit_status_error",
+        "extract_if",
+        "generic_nonzero",
+        "hash_raw_entry",
+        "hash_set_entry",
+        "impl_trait_in_assoc_type",
+        "inline_const_pat",
         "inner_deref",
         "destructuring_assignment",
         "error_in_core",
+        "io_error_more",
+        "io_error_other",
+        "io_safety",
+        "is_none_or",
+        "iter_advance_by",
+        "iter_array_chunks",
+        "iter_collect_into",
+        "iter_intersperse",
+        "iter_map_windows",
+        "iter_next_chunk",
+        "iter_partition_in_place",
         "iter_zip",
+        "iterator_try_collect",
+        "linked_list_cursors",
+        "map_first_last",
+        "maybe_uninit_slice",
+        "maybe_uninit_uninit_array",
+        "mpmc_channel",
+        "mutex_unpoison",
         "never_type",
+        "new_uninit",
+        "option_get_or_insert_default",
+        "option_take_if",
+        "panic_abort",
+        "panic_info_message",
+        "panic_update_hook",
+        "pattern",
+        "portable_simd",
+        "process_exitcode",
         "question_mark",
+        "raw_ref_macros",
+        "read_buf",
+        "result_flattening",
         "rust1",
+        "scoped_threads",
+        "slice_as_chunks",
+        "slice_fill",
+        "slice_fill_with",
+        "slice_first_last_chunk",
+        "slice_take",
         "step_trait",
+        "sync_exclusive",
+        "target_abi",
+        "target_has_atomic",
+        "thread_sleep_until",
+        "try_find",
+        "try_reserve",
+        "try_trait_v2",
         "unstable_features",
         "unwrap_infallible",
+        "vec_into_raw_parts",
+        "vec_push_within_capacity",
+        "vec_split_at_spare",
+        "wasm",
+        "windows_io_safety",
+        "write_all_vectored",
     ])))
 }
 
@@ -91,10 +174,7 @@ fn assert_enabled_features(enabled: &EnabledFeatures)
 
     fn from_enabled_features(enabled_features: &EnabledFeatures) -> HashSet<Feature>
     {
-        enabled_features
-            .iter()
-            .filter_map(|(&k, v)| v.as_ref().map(|c| (k, bset_from_hset(c))))
-            .collect()
+        enabled_only(enabled_features).map(|(&k, c)| (k, bset_from_hset(c))).collect()
     }
 
     fn assert_enabled_fits_required_and_allowed<T: Hash + Eq>(
@@ -110,16 +190,98 @@ fn assert_enabled_features(enabled: &EnabledFeatures)
 
     let required = hset![("rust1", bset!["comp", "lang", "lib"])];
     let optional = hset![
+        ("dev_compiler", bset!["comp"]),
+        ("panic_abort", bset!["comp"]),
+        ("target_abi", bset!["comp"]),
+        ("target_has_atomic", bset!["comp"]),
         ("unstable_features", bset!["comp"]),
+        ("wasm", bset!["comp"]),
         ("arbitrary_self_types", bset!["lang"]),
+        ("closure_captures", bset!["lang"]),
+        ("const_assert", bset!["lang"]),
+        ("const_fn_float_arithmetic", bset!["lang"]),
+        ("const_let_else", bset!["lang"]),
+        ("const_panic", bset!["lang"]),
         ("destructuring_assignment", bset!["lang"]),
+        ("diagnostic_namespace", bset!["lang"]),
+        ("impl_trait_in_assoc_type", bset!["lang"]),
+        ("inline_const_pat", bset!["lang"]),
         ("never_type", bset!["lang"]),
         ("question_mark", bset!["lang"]),
+        ("try_trait_v2", bset!["lang", "lib"]),
+        ("allocator_api", bset!["lib"]),
+        ("anonymous_pipe", bset!["lib"]),
+        ("atomic_from_mut", bset!["lib"]),
+        ("available_parallelism", bset!["lib"]),
+        ("backtrace", bset!["lib"]),
+        ("binary_heap_drain_sorted", bset!["lib"]),
+        ("binary_heap_into_iter_sorted", bset!["lib"]),
+        ("black_box", bset!["lib"]),
+        ("btree_cursors", bset!["lib"]),
+        ("cmp_minmax", bset!["lib"]),
+        ("const_cstr_methods", bset!["lib"]),
+        ("const_slice_first", bset!["lib"]),
+        ("core_net", bset!["lib"]),
+        ("div_duration", bset!["lib"]),
+        ("duration_millis_float", bset!["lib"]),
+        ("entry_insert", bset!["lib"]),
+        ("error_generic_member_access", bset!["lib"]),
         ("error_in_core", bset!["lib"]),
+        ("error_reporter", bset!["lib"]),
+        ("exclusive_wrapper", bset!["lib"]),
+        ("exit_status_error", bset!["lib"]),
+        ("extract_if", bset!["lib"]),
+        ("generic_nonzero", bset!["lib"]),
+        ("hash_raw_entry", bset!["lib"]),
+        ("hash_set_entry", bset!["lib"]),
         ("inner_deref", bset!["lib"]),
+        ("io_error_more", bset!["lib"]),
+        ("io_error_other", bset!["lib"]),
+        ("io_safety", bset!["lib"]),
+        ("is_none_or", bset!["lib"]),
+        ("iter_advance_by", bset!["lib"]),
+        ("iter_array_chunks", bset!["lib"]),
+        ("iter_collect_into", bset!["lib"]),
+        ("iter_intersperse", bset!["lib"]),
+        ("iter_map_windows", bset!["lib"]),
+        ("iter_next_chunk", bset!["lib"]),
+        ("iter_partition_in_place", bset!["lib"]),
         ("iter_zip", bset!["lib"]),
+        ("iterator_try_collect", bset!["lib"]),
+        ("linked_list_cursors", bset!["lib"]),
+        ("map_first_last", bset!["lib"]),
+        ("maybe_uninit_slice", bset!["lib"]),
+        ("maybe_uninit_uninit_array", bset!["lib"]),
+        ("mpmc_channel", bset!["lib"]),
+        ("mutex_unpoison", bset!["lib"]),
+        ("new_uninit", bset!["lib"]),
+        ("option_get_or_insert_default", bset!["lib"]),
+        ("option_take_if", bset!["lib"]),
+        ("panic_info_message", bset!["lib"]),
+        ("panic_update_hook", bset!["lib"]),
+        ("pattern", bset!["lib"]),
+        ("portable_simd", bset!["lib"]),
+        ("process_exitcode", bset!["lib"]),
+        ("raw_ref_macros", bset!["lib"]),
+        ("read_buf", bset!["lib"]),
+        ("result_flattening", bset!["lib"]),
+        ("scoped_threads", bset!["lib"]),
+        ("slice_as_chunks", bset!["lib"]),
+        ("slice_fill", bset!["lib"]),
+        ("slice_fill_with", bset!["lib"]),
+        ("slice_first_last_chunk", bset!["lib"]),
+        ("slice_take", bset!["lib"]),
         ("step_trait", bset!["lib"]),
-        ("unwrap_infallible", bset!["lib"])
+        ("sync_exclusive", bset!["lib"]),
+        ("thread_sleep_until", bset!["lib"]),
+        ("try_find", bset!["lib"]),
+        ("try_reserve", bset!["lib"]),
+        ("unwrap_infallible", bset!["lib"]),
+        ("vec_into_raw_parts", bset!["lib"]),
+        ("vec_push_within_capacity", bset!["lib"]),
+        ("vec_split_at_spare", bset!["lib"]),
+        ("windows_io_safety", bset!["lib"]),
+        ("write_all_vectored", bset!["lib"])
     ];
     let allowed = &required | &optional;
 